@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use aoc_common::{parse_lines, parse_token, Answer, ParseError, Solution};
+
+// `sorted` has to be sorted ascending, the values are returned in ascending order
+fn k_sum_sorted(sorted: &[i32], k: usize, target: i64) -> Option<Vec<i32>> {
+    match k {
+        0 => {
+            if target == 0 {
+                Some(Vec::new())
+            } else {
+                None
+            }
+        }
+        1 => {
+            let target = i32::try_from(target).ok()?;
+            sorted.binary_search(&target).ok().map(|_| vec![target])
+        }
+        2 => {
+            if sorted.is_empty() {
+                return None;
+            }
+            let (mut low, mut high) = (0, sorted.len() - 1);
+            while low < high {
+                let sum = sorted[low] as i64 + sorted[high] as i64;
+                if sum == target {
+                    return Some(vec![sorted[low], sorted[high]]);
+                } else if sum < target {
+                    low += 1;
+                } else {
+                    high -= 1;
+                }
+            }
+            None
+        }
+        _ => {
+            for (i, &first) in sorted.iter().enumerate() {
+                // the same first value would only find the same combinations again
+                if i > 0 && sorted[i - 1] == first {
+                    continue;
+                }
+                if let Some(mut rest) = k_sum_sorted(&sorted[i + 1..], k - 1, target - first as i64)
+                {
+                    rest.insert(0, first);
+                    return Some(rest);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Finds `k` values of `numbers` which sum up to `target`.
+///
+/// Every entry of `numbers` is used at most once, the values are returned in ascending order.
+pub fn k_sum(numbers: &[i32], k: usize, target: i32) -> Option<Vec<i32>> {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    k_sum_sorted(&sorted, k, target as i64)
+}
+
+pub fn product(values: &[i32]) -> i64 {
+    values.iter().map(|&value| value as i64).product()
+}
+
+/// Iterator over all combinations of `k` indices whose numbers sum up to a target.
+///
+/// Created by [`combinations_summing_to`], the indices of each combination are ascending and the
+/// combinations are yielded in lexicographic order.
+pub struct Combinations<'a> {
+    numbers: &'a [i32],
+    target: i64,
+    indices: Option<Vec<usize>>,
+    seen: Option<HashSet<Vec<i32>>>,
+}
+
+/// Enumerates every combination of `k` distinct indices into `numbers` which sum up to `target`.
+pub fn combinations_summing_to(numbers: &[i32], k: usize, target: i32) -> Combinations<'_> {
+    Combinations {
+        numbers,
+        target: target as i64,
+        indices: if k <= numbers.len() {
+            Some((0..k).collect())
+        } else {
+            None
+        },
+        seen: None,
+    }
+}
+
+impl Combinations<'_> {
+    /// Only yield the first combination of each set of values, so repeated numbers don't yield the
+    /// same values again with different indices.
+    pub fn dedup(mut self) -> Self {
+        self.seen = Some(HashSet::new());
+        self
+    }
+
+    // Moves `indices` to the next combination, or to `None` after the last one
+    fn advance(&mut self) {
+        let n = self.numbers.len();
+        if let Some(indices) = &mut self.indices {
+            let k = indices.len();
+            match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+                Some(i) => {
+                    indices[i] += 1;
+                    for j in i + 1..k {
+                        indices[j] = indices[j - 1] + 1;
+                    }
+                }
+                None => self.indices = None,
+            }
+        }
+    }
+}
+
+impl Iterator for Combinations<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let numbers = self.numbers;
+            let indices = self.indices.clone()?;
+            self.advance();
+
+            let sum: i64 = indices.iter().map(|&i| numbers[i] as i64).sum();
+            if sum != self.target {
+                continue;
+            }
+            if let Some(seen) = &mut self.seen {
+                let mut values: Vec<_> = indices.iter().map(|&i| numbers[i]).collect();
+
+                values.sort_unstable();
+                if !seen.insert(values) {
+                    continue;
+                }
+            }
+            return Some(indices);
+        }
+    }
+}
+
+pub struct ExpenseReport {
+    numbers: Vec<i32>,
+}
+
+impl Solution for ExpenseReport {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let numbers = parse_lines(input, |line| parse_token(line, line, "a number"))?;
+        Ok(ExpenseReport { numbers })
+    }
+
+    fn part_1(&self) -> Answer {
+        k_sum(&self.numbers, 2, 2020)
+            .map(|values| product(&values))
+            .into()
+    }
+
+    fn part_2(&self) -> Answer {
+        k_sum(&self.numbers, 3, 2020)
+            .map(|values| product(&values))
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST_INPUT: &str = "1721\n979\n366\n299\n675\n1456";
+
+    #[test]
+    fn test_part_1() {
+        let report = ExpenseReport::parse(TEST_INPUT).unwrap();
+        assert_eq!(report.part_1(), Answer::Signed(514579));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let report = ExpenseReport::parse(TEST_INPUT).unwrap();
+        assert_eq!(report.part_2(), Answer::Signed(241861950));
+    }
+
+    #[test]
+    fn test_k_sum() {
+        let numbers = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(k_sum(&numbers, 2, 2020), Some(vec![299, 1721]));
+        assert_eq!(k_sum(&numbers, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(
+            k_sum(&numbers, 4, 366 + 299 + 675 + 1456),
+            Some(vec![299, 366, 675, 1456])
+        );
+        assert_eq!(k_sum(&numbers, 1, 979), Some(vec![979]));
+        assert_eq!(k_sum(&numbers, 2, 3), None);
+        assert_eq!(k_sum(&numbers, 7, 0), None);
+        assert_eq!(k_sum(&[], 0, 0), Some(vec![]));
+    }
+
+    #[test]
+    fn test_k_sum_uses_every_entry_once() {
+        assert_eq!(k_sum(&[1010], 2, 2020), None);
+        assert_eq!(k_sum(&[1010, 1010], 2, 2020), Some(vec![1010, 1010]));
+        assert_eq!(k_sum(&[-5, 3, 10, -2], 3, 3), Some(vec![-5, -2, 10]));
+    }
+
+    #[test]
+    fn test_combinations() {
+        let numbers = [1721, 979, 366, 299, 675, 1456];
+        let found: Vec<_> = combinations_summing_to(&numbers, 2, 2020).collect();
+        assert_eq!(found, vec![vec![0, 3]]);
+        let found: Vec<_> = combinations_summing_to(&numbers, 3, 2020).collect();
+        assert_eq!(found, vec![vec![1, 2, 4]]);
+    }
+
+    #[test]
+    fn test_combinations_with_duplicates() {
+        let numbers = [5, 5, 5, 10];
+        let found: Vec<_> = combinations_summing_to(&numbers, 2, 10).collect();
+        assert_eq!(found, vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+        let found: Vec<_> = combinations_summing_to(&numbers, 2, 10).dedup().collect();
+        assert_eq!(found, vec![vec![0, 1]]);
+        let found: Vec<_> = combinations_summing_to(&numbers, 3, 20).dedup().collect();
+        assert_eq!(found, vec![vec![0, 1, 3]]);
+    }
+
+    #[test]
+    fn test_combinations_with_negatives() {
+        let numbers = [-3, 7, 4, -1, 0];
+        let found: Vec<_> = combinations_summing_to(&numbers, 2, 4).collect();
+        assert_eq!(found, vec![vec![0, 1], vec![2, 4]]);
+        let found: Vec<_> = combinations_summing_to(&numbers, 3, 0).collect();
+        assert_eq!(found, vec![vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn test_combinations_empty() {
+        assert_eq!(combinations_summing_to(&[], 2, 0).count(), 0);
+        assert_eq!(combinations_summing_to(&[], 0, 0).count(), 1);
+        assert_eq!(combinations_summing_to(&[1, 2], 3, 3).count(), 0);
+    }
+}
@@ -0,0 +1,73 @@
+use aoc_common::{parse_lines, parse_token, Answer, ParseError, Solution};
+
+pub struct Adapters {
+    /// Sorted, without gaps of more than 3 jolts
+    adapters: Vec<u32>,
+}
+
+impl Solution for Adapters {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let joltages: Vec<u32> = parse_lines(input, |line| parse_token(line, line, "a joltage"))?;
+        // sorted with their lines, to point at the adapter after a gap
+        let mut adapters: Vec<_> = joltages.into_iter().zip(input.lines()).collect();
+        adapters.sort_unstable();
+        // the charging outlet has 0 jolts
+        let mut previous = 0;
+        for &(adapter, line) in &adapters {
+            if adapter - previous > 3 {
+                return Err(ParseError::new(
+                    input,
+                    line,
+                    format!("an adapter of at most {} jolts", previous + 3),
+                ));
+            }
+            previous = adapter;
+        }
+        Ok(Adapters {
+            adapters: adapters.into_iter().map(|(adapter, _)| adapter).collect(),
+        })
+    }
+
+    fn part_1(&self) -> Answer {
+        let mut one_jolt_diff = 0u32;
+        let mut three_jolt_diff = 0u32;
+        // charging outlet 0 jolts
+        let mut current_joltage = 0;
+        for &adapter in &self.adapters {
+            let diff = adapter - current_joltage;
+            if diff == 1 {
+                one_jolt_diff += 1;
+            } else if diff == 3 {
+                three_jolt_diff += 1;
+            }
+            current_joltage = adapter;
+        }
+        // device jolts = max + 3
+        three_jolt_diff += 1;
+
+        (one_jolt_diff * three_jolt_diff).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let adapters = Adapters::parse("16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4").unwrap();
+        assert_eq!(adapters.part_1(), Answer::Unsigned(7 * 5));
+    }
+
+    #[test]
+    fn test_gap() {
+        let err = Adapters::parse("9\n1\n2").err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (1, "9"));
+        assert_eq!(err.expected, "an adapter of at most 5 jolts");
+        assert!(Adapters::parse("4").is_err());
+    }
+}
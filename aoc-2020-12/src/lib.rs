@@ -0,0 +1,223 @@
+use std::str::FromStr;
+
+use aoc_common::{parse_lines, parse_token, Answer, ParseError, Solution};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Action {
+    North(i32),
+    South(i32),
+    East(i32),
+    West(i32),
+    Left(i32),
+    Right(i32),
+    Forward(i32),
+}
+
+impl FromStr for Action {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let action_len = s
+            .chars()
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "an action"))?
+            .len_utf8();
+        let (action, value) = s.split_at(action_len);
+
+        let value = parse_token(s, value, "a number")?;
+        if (action == "L" || action == "R") && ![0, 90, 180, 270].contains(&value) {
+            return Err(ParseError::new(
+                s,
+                &s[action_len..],
+                "0, 90, 180 or 270 degrees",
+            ));
+        }
+
+        match action {
+            "N" => Ok(Action::North(value)),
+            "E" => Ok(Action::East(value)),
+            "S" => Ok(Action::South(value)),
+            "W" => Ok(Action::West(value)),
+            "L" => Ok(Action::Left(value)),
+            "R" => Ok(Action::Right(value)),
+            "F" => Ok(Action::Forward(value)),
+            _ => Err(ParseError::new(s, action, "one of N, E, S, W, L, R or F")),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    fn to_degrees(self) -> i32 {
+        match self {
+            Direction::North => 0,
+            Direction::East => 90,
+            Direction::South => 180,
+            Direction::West => 270,
+        }
+    }
+
+    fn from_degrees(degrees: i32) -> Direction {
+        let degrees = degrees % 360;
+        match degrees {
+            -90 => Direction::West,
+            -180 => Direction::South,
+            -270 => Direction::East,
+            0 => Direction::North,
+            90 => Direction::East,
+            180 => Direction::South,
+            270 => Direction::West,
+            _ => panic!("No valid direction: {}", degrees),
+        }
+    }
+
+    fn turn_left(&mut self, degrees: i32) {
+        let degrees = self.to_degrees() - degrees;
+        *self = Direction::from_degrees(degrees);
+    }
+    fn turn_right(&mut self, degrees: i32) {
+        let degrees = self.to_degrees() + degrees;
+        *self = Direction::from_degrees(degrees);
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Ship {
+    x: i32,
+    y: i32,
+    direction: Direction,
+    waypoint: Point,
+}
+
+impl Ship {
+    pub fn new() -> Ship {
+        Ship {
+            x: 0,
+            y: 0,
+            direction: Direction::East,
+            waypoint: Point { x: 10, y: 1 },
+        }
+    }
+
+    pub fn apply_action_part_1(&mut self, action: Action) {
+        match action {
+            Action::North(value) => self.y += value,
+            Action::South(value) => self.y -= value,
+            Action::East(value) => self.x += value,
+            Action::West(value) => self.x -= value,
+            Action::Left(value) => self.direction.turn_left(value),
+            Action::Right(value) => self.direction.turn_right(value),
+            Action::Forward(value) => match self.direction {
+                Direction::North => self.y += value,
+                Direction::South => self.y -= value,
+                Direction::East => self.x += value,
+                Direction::West => self.x -= value,
+            },
+        }
+    }
+
+    pub fn apply_action_part_2(&mut self, action: Action) {
+        match action {
+            Action::North(value) => self.waypoint.y += value,
+            Action::South(value) => self.waypoint.y -= value,
+            Action::East(value) => self.waypoint.x += value,
+            Action::West(value) => self.waypoint.x -= value,
+            Action::Left(0) | Action::Right(0) => {}
+            Action::Left(90) | Action::Right(270) => {
+                let new_point = Point {
+                    x: -self.waypoint.y,
+                    y: self.waypoint.x,
+                };
+                self.waypoint = new_point;
+            }
+            Action::Left(180) | Action::Right(180) => {
+                let new_point = Point {
+                    x: -self.waypoint.x,
+                    y: -self.waypoint.y,
+                };
+                self.waypoint = new_point;
+            }
+            Action::Left(270) | Action::Right(90) => {
+                let new_point = Point {
+                    x: self.waypoint.y,
+                    y: -self.waypoint.x,
+                };
+                self.waypoint = new_point;
+            }
+            Action::Left(value) | Action::Right(value) => {
+                panic!("Invalid value: {}", value);
+            }
+            Action::Forward(value) => {
+                self.y += self.waypoint.y * value;
+                self.x += self.waypoint.x * value;
+            }
+        }
+    }
+
+    pub fn part_1(&mut self, actions: &[Action]) -> i32 {
+        for action in actions {
+            self.apply_action_part_1(*action);
+        }
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn part_2(&mut self, actions: &[Action]) -> i32 {
+        for action in actions {
+            self.apply_action_part_2(*action);
+        }
+        self.x.abs() + self.y.abs()
+    }
+}
+
+pub struct Instructions {
+    actions: Vec<Action>,
+}
+
+impl Solution for Instructions {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let actions = parse_lines(input, Action::from_str)?;
+        Ok(Instructions { actions })
+    }
+
+    fn part_1(&self) -> Answer {
+        let mut ship = Ship::new();
+        ship.part_1(&self.actions).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        let mut ship = Ship::new();
+        // 167123 too high
+        ship.part_2(&self.actions).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST_INPUT: &str = "F10\nN3\nF7\nR90\nF11";
+
+    #[test]
+    fn test_part_1() {
+        let instructions = Instructions::parse(TEST_INPUT).unwrap();
+        assert_eq!(instructions.part_1(), Answer::Signed(25));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let instructions = Instructions::parse(TEST_INPUT).unwrap();
+        assert_eq!(instructions.part_2(), Answer::Signed(286));
+    }
+}
@@ -0,0 +1,119 @@
+use aoc_common::{parse_token, Answer, ParseError, Solution};
+
+fn find_earliest_timestamp(input: &str) -> usize {
+    let mut buses: Vec<(usize, usize)> = input
+        .split(',')
+        .enumerate()
+        .filter_map(|s| s.1.parse().ok().map(|parsed| (s.0, parsed)))
+        .collect();
+
+    buses.sort_by_key(|bus| std::cmp::Reverse(bus.1));
+
+    'outer: for t in 1.. {
+        let t = t * buses[0].1 - buses[0].0;
+        for bus in buses[1..].iter() {
+            if !(t + bus.0).is_multiple_of(bus.1) {
+                continue 'outer;
+            }
+        }
+        return t;
+    }
+    panic!("No solution found!");
+}
+
+pub struct Notes {
+    earliest_start: usize,
+    schedule: String,
+}
+
+impl Solution for Notes {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+        let earliest_start = lines
+            .next()
+            .ok_or_else(|| ParseError::missing(input, "a timestamp"))?;
+        let earliest_start = parse_token(input, earliest_start, "a timestamp")?;
+        let schedule = lines
+            .next()
+            .ok_or_else(|| ParseError::missing(input, "a bus schedule"))?;
+        let mut buses = 0;
+        for bus in schedule.split(',') {
+            if bus != "x" {
+                // the same type as in the parts, a larger ID would be dropped there
+                let id: usize = parse_token(input, bus, "a bus ID or 'x'")?;
+                if id == 0 {
+                    return Err(ParseError::new(input, bus, "a bus ID of at least 1"));
+                }
+                buses += 1;
+            }
+        }
+        if buses == 0 {
+            return Err(ParseError::new(input, schedule, "at least one bus ID"));
+        }
+        Ok(Notes {
+            earliest_start,
+            schedule: schedule.into(),
+        })
+    }
+
+    fn part_1(&self) -> Answer {
+        let buses: Vec<usize> = self
+            .schedule
+            .split(',')
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        let min = buses
+            .iter()
+            .map(|bus| (self.earliest_start / bus + 1) * bus)
+            .enumerate()
+            .min_by_key(|x| x.1)
+            .unwrap();
+
+        (buses[min.0] * (min.1 - self.earliest_start)).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        find_earliest_timestamp(&self.schedule).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST_INPUTS: [&str; 6] = [
+        "17,x,13,19",
+        "67,7,59,61",
+        "67,x,7,59,61",
+        "67,7,x,59,61",
+        "1789,37,47,1889",
+        "7,13,x,x,59,x,31,19",
+    ];
+    const RESULTS: [usize; 6] = [3417, 754018, 779210, 1261476, 1202161486, 1068781];
+
+    #[test]
+    fn test_part_1() {
+        let notes = Notes::parse("939\n7,13,x,x,59,x,31,19").unwrap();
+        assert_eq!(notes.part_1(), Answer::Unsigned(295));
+
+        // IDs beyond u32
+        let notes = Notes::parse("4294967296\nx,4294967297").unwrap();
+        assert_eq!(notes.part_1(), Answer::Unsigned(4294967297));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Notes::parse("939\n0,x,13").err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "0"));
+        let err = Notes::parse("939\nx,x").err().unwrap();
+        assert_eq!(err.expected, "at least one bus ID");
+    }
+
+    #[test]
+    fn test_part_2() {
+        for (input, result) in TEST_INPUTS.iter().zip(&RESULTS) {
+            let test_result = find_earliest_timestamp(input);
+            assert_eq!(test_result, *result);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use aoc_common::{parse_lines, parse_token, Answer, ParseError, Solution};
+use gray_codes::VecSubsets;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Instruction {
+    SetMask(u64, u64, u64, Vec<usize>),
+    SetMemory(u64, u64),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.splitn(2, '=');
+        let token = tokens.next().unwrap_or_default().trim();
+        let value = tokens
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "'='"))?
+            .trim();
+        match token {
+            "mask" => {
+                if value.len() != 36 {
+                    return Err(ParseError::new(s, value, "a mask of 36 bits"));
+                }
+                let mut bitmask_zeros = u64::MAX;
+                let mut bitmask_ones = 0;
+                let mut bitmask_x = 0;
+                let mut bitmask_x_pos = Vec::new();
+                for (index, (offset, c)) in value.char_indices().rev().enumerate() {
+                    match c {
+                        'X' => {
+                            bitmask_x |= 1 << index;
+                            bitmask_x_pos.push(index);
+                        }
+                        '1' => {
+                            bitmask_ones |= 1 << index;
+                        }
+                        '0' => {
+                            bitmask_zeros &= !(1 << index);
+                        }
+                        _ => {
+                            let found = &value[offset..offset + c.len_utf8()];
+                            return Err(ParseError::new(s, found, "'X', '1' or '0'"));
+                        }
+                    }
+                }
+                Ok(Instruction::SetMask(
+                    bitmask_zeros,
+                    bitmask_ones,
+                    bitmask_x,
+                    bitmask_x_pos,
+                ))
+            }
+            mem => {
+                let address = mem
+                    .strip_prefix("mem[")
+                    .and_then(|address| address.strip_suffix(']'))
+                    .ok_or_else(|| ParseError::new(s, mem, "\"mask\" or \"mem[<address>]\""))?;
+                let address = parse_token(s, address, "an address")?;
+                let value = parse_token(s, value, "a value")?;
+                Ok(Instruction::SetMemory(address, value))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Machine {
+    bitmask_zeros: u64,
+    bitmask_ones: u64,
+    bitmask_x: u64,
+    bitmask_x_pos: Vec<usize>,
+
+    memory: HashMap<u64, u64>,
+}
+
+impl Machine {
+    fn new() -> Machine {
+        Machine {
+            bitmask_zeros: u64::MAX,
+            bitmask_ones: 0,
+            bitmask_x: 0,
+            bitmask_x_pos: Vec::new(),
+            memory: HashMap::new(),
+        }
+    }
+
+    fn execute_instruction(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::SetMask(bitmask_zeros, bitmask_ones, _, _) => {
+                self.bitmask_zeros = bitmask_zeros;
+                self.bitmask_ones = bitmask_ones;
+            }
+            Instruction::SetMemory(address, value) => {
+                let mem_value = self.memory.entry(address).or_default();
+                *mem_value = value;
+                *mem_value &= self.bitmask_zeros;
+                *mem_value |= self.bitmask_ones;
+            }
+        }
+    }
+
+    fn part_1(&mut self, instructions: &[Instruction]) -> u64 {
+        for instruction in instructions {
+            self.execute_instruction(instruction.clone());
+        }
+        self.memory.values().sum()
+    }
+
+    fn execute_instruction_part_2(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::SetMask(bitmask_zeros, bitmask_ones, bitmask_x, bitmask_x_pos) => {
+                self.bitmask_zeros = bitmask_zeros;
+                self.bitmask_ones = bitmask_ones;
+                self.bitmask_x = bitmask_x;
+                self.bitmask_x_pos = bitmask_x_pos;
+            }
+            Instruction::SetMemory(address, value) => {
+                let address = (address | self.bitmask_ones) & (!self.bitmask_x);
+                for subset in VecSubsets::of(&self.bitmask_x_pos) {
+                    let mut address = address;
+                    for bit in subset {
+                        address |= 1 << *bit;
+                    }
+                    *self.memory.entry(address).or_insert(0) = value;
+                }
+            }
+        }
+    }
+
+    fn part_2(&mut self, instructions: &[Instruction]) -> u64 {
+        for instruction in instructions {
+            self.execute_instruction_part_2(instruction.clone());
+        }
+        self.memory.values().sum()
+    }
+}
+
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Solution for Program {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let instructions = parse_lines(input, Instruction::from_str)?;
+        Ok(Program { instructions })
+    }
+
+    fn part_1(&self) -> Answer {
+        let mut machine = Machine::new();
+        machine.part_1(&self.instructions).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        let mut machine = Machine::new();
+        machine.part_2(&self.instructions).into()
+    }
+}
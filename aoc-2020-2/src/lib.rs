@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use aoc_common::{parse_lines, parse_token, Answer, ParseError, Solution};
+
+mod policy;
+
+pub use policy::{
+    parse_policy, CountRange, ForbiddenLetters, Matches, Policies, Policy, PositionalAnd,
+    PositionalXor, Violation, POLICY_NAMES,
+};
+
+pub struct Range {
+    pub min: usize,
+    pub max: usize,
+}
+
+/// One line of the password database.
+pub struct Rule {
+    pub range: Range,
+    pub letter: u8,
+    pub string: String,
+}
+
+impl FromStr for Range {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.splitn(2, '-');
+        let min = tokens.next().unwrap_or_default();
+        let max = tokens.next().ok_or_else(|| ParseError::missing(s, "'-'"))?;
+        Ok(Range {
+            min: parse_token(s, min, "a number")?,
+            max: parse_token(s, max, "a number")?,
+        })
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let range = tokens
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a range"))?;
+        let range = Range::from_str(range).map_err(|err| err.within(s, range))?;
+        let letter = tokens
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a letter"))?;
+        let letter = match letter.as_bytes() {
+            [letter, b':'] => *letter,
+            _ => return Err(ParseError::new(s, letter, "a letter followed by ':'")),
+        };
+        let string = tokens
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a password"))?
+            .to_string();
+        if let Some(token) = tokens.next() {
+            return Err(ParseError::new(s, token, "end of line"));
+        }
+        Ok(Rule {
+            range,
+            letter,
+            string,
+        })
+    }
+}
+
+pub struct PasswordDatabase {
+    rules: Vec<Rule>,
+}
+
+/// The result of checking one line of the password database.
+pub struct ReportEntry<'a> {
+    pub line: usize,
+    pub rule: &'a Rule,
+    pub violations: Vec<Violation>,
+}
+
+impl PasswordDatabase {
+    pub fn count_valid(&self, policy: &dyn Policy) -> usize {
+        self.rules
+            .iter()
+            .filter(|rule| policy.is_valid(rule))
+            .count()
+    }
+
+    /// Checks every line against all policies, lines are 1-based.
+    pub fn report<'a>(
+        &'a self,
+        policies: &'a Policies,
+    ) -> impl Iterator<Item = ReportEntry<'a>> + 'a {
+        self.rules
+            .iter()
+            .enumerate()
+            .map(move |(i, rule)| ReportEntry {
+                line: i + 1,
+                rule,
+                violations: policies.violations(rule),
+            })
+    }
+}
+
+impl Solution for PasswordDatabase {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let rules = parse_lines(input, Rule::from_str)?;
+        Ok(PasswordDatabase { rules })
+    }
+
+    fn part_1(&self) -> Answer {
+        self.count_valid(&CountRange).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        self.count_valid(&PositionalXor).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST_INPUT: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+
+    #[test]
+    fn test_part_1() {
+        let database = PasswordDatabase::parse(TEST_INPUT).unwrap();
+        assert_eq!(database.part_1(), Answer::Unsigned(2));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let database = PasswordDatabase::parse(TEST_INPUT).unwrap();
+        assert_eq!(database.part_2(), Answer::Unsigned(1));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = PasswordDatabase::parse("1-3 a: abcde\n1-x b: cdefg")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a number");
+
+        let err = PasswordDatabase::parse("1-3 a abcde").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 5));
+    }
+
+    #[test]
+    fn test_policies() {
+        let database = PasswordDatabase::parse(TEST_INPUT).unwrap();
+        assert_eq!(database.count_valid(&PositionalAnd), 1);
+        assert_eq!(database.count_valid(&ForbiddenLetters("bd".into())), 1);
+        assert_eq!(
+            database.count_valid(&*parse_policy("regex ^c+$").unwrap()),
+            1
+        );
+
+        let config = "# corporate rules\ncount-range\n\nforbidden e\n";
+        let policies = Policies::parse(config).unwrap();
+        assert_eq!(database.count_valid(&policies), 1);
+    }
+
+    #[test]
+    fn test_policy_errors() {
+        let err = Policies::parse("count-range\n  regex [a-").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 9));
+        let err = Policies::parse("count-range 3").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 13));
+        let err = Policies::parse("unknown").err().unwrap();
+        assert_eq!(err.text, "unknown");
+    }
+
+    #[test]
+    fn test_positions_out_of_range() {
+        let database = PasswordDatabase::parse("0-9 a: abc").unwrap();
+        assert_eq!(database.count_valid(&PositionalXor), 0);
+    }
+
+    #[test]
+    fn test_report() {
+        let database = PasswordDatabase::parse(TEST_INPUT).unwrap();
+        let policies = Policies(vec![Box::new(CountRange), Box::new(PositionalXor)]);
+        let report: Vec<_> = database.report(&policies).collect();
+        assert!(report[0].violations.is_empty());
+        assert_eq!(
+            report[1].violations,
+            vec![
+                Violation {
+                    policy: "count-range",
+                    reason: "letter 'b' appears 0 times, allowed 1-3".into()
+                },
+                Violation {
+                    policy: "positional-xor",
+                    reason: "letter 'b' at neither position 1 nor 3".into()
+                }
+            ]
+        );
+        assert_eq!(report[2].line, 3);
+        assert_eq!(
+            report[2].violations[0].reason,
+            "letter 'c' at both positions 2 and 9"
+        );
+
+        let database = PasswordDatabase::parse("1-9 a: abcde").unwrap();
+        let report: Vec<_> = database.report(&policies).collect();
+        assert_eq!(
+            report[0].violations[0].reason,
+            "position 9 out of range for 5 letters"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use aoc_common::{parse_lines, ParseError};
+use regex::Regex;
+
+use crate::Rule;
+
+/// A password policy which can be checked against the entries of the password database.
+pub trait Policy {
+    fn name(&self) -> &'static str;
+
+    /// Checks the entry, the error describes why the password violates the policy.
+    fn check(&self, rule: &Rule) -> Result<(), String>;
+
+    fn is_valid(&self, rule: &Rule) -> bool {
+        self.check(rule).is_ok()
+    }
+}
+
+/// A policy violated by an entry and the reason why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub policy: &'static str,
+    pub reason: String,
+}
+
+/// The letter has to appear within the range of the entry (part 1).
+pub struct CountRange;
+
+/// The letter has to be at exactly one of the two positions of the entry (part 2).
+pub struct PositionalXor;
+
+/// The letter has to be at both positions of the entry.
+pub struct PositionalAnd;
+
+/// None of the letters may appear in the password.
+pub struct ForbiddenLetters(pub String);
+
+/// The password has to match the regex.
+pub struct Matches(pub Regex);
+
+// Whether the letter of the rule is at the 1-based `position` of its password
+fn letter_at(rule: &Rule, position: usize) -> Result<bool, String> {
+    match position
+        .checked_sub(1)
+        .and_then(|index| rule.string.as_bytes().get(index))
+    {
+        Some(&letter) => Ok(letter == rule.letter),
+        None => Err(format!(
+            "position {} out of range for {} letters",
+            position,
+            rule.string.len()
+        )),
+    }
+}
+
+fn positions_error(rule: &Rule, found: bool) -> String {
+    if found {
+        format!(
+            "letter '{}' at both positions {} and {}",
+            rule.letter as char, rule.range.min, rule.range.max
+        )
+    } else {
+        format!(
+            "letter '{}' at neither position {} nor {}",
+            rule.letter as char, rule.range.min, rule.range.max
+        )
+    }
+}
+
+impl Policy for CountRange {
+    fn name(&self) -> &'static str {
+        "count-range"
+    }
+
+    fn check(&self, rule: &Rule) -> Result<(), String> {
+        let count = rule.string.bytes().filter(|c| *c == rule.letter).count();
+        if (rule.range.min..=rule.range.max).contains(&count) {
+            Ok(())
+        } else {
+            Err(format!(
+                "letter '{}' appears {} times, allowed {}-{}",
+                rule.letter as char, count, rule.range.min, rule.range.max
+            ))
+        }
+    }
+}
+
+impl Policy for PositionalXor {
+    fn name(&self) -> &'static str {
+        "positional-xor"
+    }
+
+    fn check(&self, rule: &Rule) -> Result<(), String> {
+        let first = letter_at(rule, rule.range.min)?;
+        let second = letter_at(rule, rule.range.max)?;
+        if first != second {
+            Ok(())
+        } else {
+            Err(positions_error(rule, first))
+        }
+    }
+}
+
+impl Policy for PositionalAnd {
+    fn name(&self) -> &'static str {
+        "positional-and"
+    }
+
+    fn check(&self, rule: &Rule) -> Result<(), String> {
+        let first = letter_at(rule, rule.range.min)?;
+        let second = letter_at(rule, rule.range.max)?;
+        if first && second {
+            Ok(())
+        } else {
+            Err(positions_error(rule, first || second))
+        }
+    }
+}
+
+impl Policy for ForbiddenLetters {
+    fn name(&self) -> &'static str {
+        "forbidden"
+    }
+
+    fn check(&self, rule: &Rule) -> Result<(), String> {
+        match rule.string.chars().find(|&c| self.0.contains(c)) {
+            Some(c) => Err(format!("contains forbidden letter '{}'", c)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Policy for Matches {
+    fn name(&self) -> &'static str {
+        "regex"
+    }
+
+    fn check(&self, rule: &Rule) -> Result<(), String> {
+        if self.0.is_match(&rule.string) {
+            Ok(())
+        } else {
+            Err(format!("does not match /{}/", self.0))
+        }
+    }
+}
+
+pub const POLICY_NAMES: [&str; 5] = [
+    "count-range",
+    "positional-xor",
+    "positional-and",
+    "forbidden",
+    "regex",
+];
+
+/// Parses a single policy description like `count-range` or `forbidden iol`.
+///
+/// `forbidden` takes the forbidden letters and `regex` the pattern as argument, the other policies
+/// don't take any.
+pub fn parse_policy(input: &str) -> Result<Box<dyn Policy>, ParseError> {
+    let s = input.trim();
+    let (name, argument) = match s.find(char::is_whitespace) {
+        Some(i) => (&s[..i], s[i..].trim()),
+        None => (s, &s[s.len()..]),
+    };
+    let no_argument = |policy: Box<dyn Policy>| {
+        if argument.is_empty() {
+            Ok(policy)
+        } else {
+            Err(ParseError::new(input, argument, "end of line"))
+        }
+    };
+    match name {
+        "count-range" => no_argument(Box::new(CountRange)),
+        "positional-xor" => no_argument(Box::new(PositionalXor)),
+        "positional-and" => no_argument(Box::new(PositionalAnd)),
+        "forbidden" if argument.is_empty() => Err(ParseError::missing(input, "forbidden letters")),
+        "forbidden" => Ok(Box::new(ForbiddenLetters(argument.into()))),
+        "regex" if argument.is_empty() => Err(ParseError::missing(input, "a regex")),
+        "regex" => match Regex::new(argument) {
+            Ok(regex) => Ok(Box::new(Matches(regex))),
+            Err(_) => Err(ParseError::new(input, argument, "a valid regex")),
+        },
+
+        _ => Err(ParseError::new(
+            input,
+            name,
+            format!("one of {}", POLICY_NAMES.join(", ")),
+        )),
+    }
+}
+
+/// Policies which all have to be fulfilled by a password.
+pub struct Policies(pub Vec<Box<dyn Policy>>);
+
+impl Policies {
+    /// Parses a policy file with one policy per line, empty lines and lines starting with `#` are
+    /// ignored.
+    pub fn parse(config: &str) -> Result<Policies, ParseError> {
+        let policies = parse_lines(config, |line| {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                Ok(None)
+            } else {
+                parse_policy(line).map(Some)
+            }
+        })?;
+        Ok(Policies(policies.into_iter().flatten().collect()))
+    }
+
+    /// Every policy the entry violates.
+    pub fn violations(&self, rule: &Rule) -> Vec<Violation> {
+        self.0
+            .iter()
+            .filter_map(|policy| {
+                policy.check(rule).err().map(|reason| Violation {
+                    policy: policy.name(),
+                    reason,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Policy for Policies {
+    fn name(&self) -> &'static str {
+        "all"
+    }
+
+    fn check(&self, rule: &Rule) -> Result<(), String> {
+        match self.violations(rule).into_iter().next() {
+            Some(violation) => Err(format!("{}: {}", violation.policy, violation.reason)),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::str::FromStr;
+
+use aoc_common::{parse_lines, Answer, ParseError, Solution};
+
+mod render;
+
+pub use render::{Rendering, Square};
+
+/// The number of trees hit going `dx` right and `dy` down per step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlopeCount {
+    pub dx: usize,
+    pub dy: usize,
+    pub trees: usize,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// What happens when a path reaches an edge of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// The map repeats to the right, the path ends at the bottom (the puzzle)
+    Wrap,
+    /// The map repeats in both directions, the path ends when it is back at the start
+    Toroidal,
+    /// The path slides down the right edge and ends at the bottom
+    Clamp,
+    /// The path ends at the right edge or the bottom
+    Stop,
+}
+
+pub const BOUNDARY_NAMES: [&str; 4] = ["wrap", "toroidal", "clamp", "stop"];
+
+impl FromStr for Boundary {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wrap" => Ok(Boundary::Wrap),
+            "toroidal" => Ok(Boundary::Toroidal),
+            "clamp" => Ok(Boundary::Clamp),
+            "stop" => Ok(Boundary::Stop),
+            _ => Err(ParseError::new(
+                s,
+                s,
+                format!("one of {}", BOUNDARY_NAMES.join(", ")),
+            )),
+        }
+    }
+}
+
+/// How rows shorter than the widest row are handled when parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaggedRows {
+    /// All rows need the width of the first row
+    Reject,
+    /// Short rows are filled up with open squares
+    Pad,
+}
+
+#[derive(Debug)]
+pub struct Map {
+    trees: Vec<Vec<bool>>,
+    width: usize,
+    boundary: Boundary,
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Map::parse_rows(value, RaggedRows::Reject)
+    }
+}
+
+impl Map {
+    /// Parses the map, rows of different widths are handled according to `ragged`.
+    pub fn parse_rows(input: &str, ragged: RaggedRows) -> Result<Map, ParseError> {
+        let mut trees: Vec<Vec<bool>> = parse_lines(input, |line| {
+            line.char_indices()
+                .map(|(i, c)| match c {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(ParseError::new(
+                        line,
+                        &line[i..i + c.len_utf8()],
+                        "'.' or '#'",
+                    )),
+                })
+                .collect()
+        })?;
+        let width = match ragged {
+            RaggedRows::Reject => {
+                let width = trees.first().map_or(0, Vec::len);
+                if let Some(line) = input.lines().find(|line| line.len() != width) {
+                    return Err(ParseError::new(
+                        input,
+                        line,
+                        format!("a row of {} squares like the first row", width),
+                    ));
+                }
+                width
+            }
+            RaggedRows::Pad => {
+                let width = trees.iter().map(Vec::len).max().unwrap_or(0);
+                for row in &mut trees {
+                    row.resize(width, false);
+                }
+                width
+            }
+        };
+        Ok(Map {
+            trees,
+            width,
+            boundary: Boundary::Wrap,
+        })
+    }
+
+    pub fn with_boundary(self, boundary: Boundary) -> Map {
+        Map { boundary, ..self }
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.trees.len()
+    }
+
+    /// Whether there is a tree at the position, the map repeats in both directions.
+    pub fn check_position(&self, x: usize, y: usize) -> bool {
+        self.trees[y % self.height()][x % self.width]
+    }
+
+    /// The positions visited going `dx` right and `dy` down per step, starting in the top left
+    /// corner. Depending on the boundary `x` and `y` keep growing past the edges of the map.
+    ///
+    /// Panics if `dy` is 0.
+    pub fn path(&self, dx: usize, dy: usize) -> Vec<(usize, usize)> {
+        assert!(dy > 0, "dy has to be at least 1");
+        let (width, height) = (self.width, self.height());
+        if width == 0 || height == 0 {
+            return Vec::new();
+        }
+        let steps = (0..).map(|step| (step * dx, step * dy));
+        match self.boundary {
+            Boundary::Wrap => steps.take_while(|&(_, y)| y < height).collect(),
+            Boundary::Toroidal => {
+                // the path is periodic, it is back at the start once both coordinates are
+                let lcm = |a: usize, b: usize| a / gcd(a, b) * b;
+                let period = lcm(width / gcd(width, dx), height / gcd(height, dy));
+                steps.take(period).collect()
+            }
+            Boundary::Clamp => steps
+                .take_while(|&(_, y)| y < height)
+                .map(|(x, y)| (x.min(width - 1), y))
+                .collect(),
+            Boundary::Stop => steps
+                .take_while(|&(x, y)| x < width && y < height)
+                .collect(),
+        }
+    }
+
+    pub fn check_slope(&self, dx: usize, dy: usize) -> usize {
+        self.path(dx, dy)
+            .into_iter()
+            .filter(|&(x, y)| self.check_position(x, y))
+            .count()
+    }
+
+    /// Counts the trees of every slope with `dx <= max_dx` and `1 <= dy <= max_dy`, ranked from
+    /// the fewest to the most trees.
+    ///
+    /// Only reduced slopes are checked, `(2, 2)` is the same direction as `(1, 1)`.
+    pub fn sweep_slopes(&self, max_dx: usize, max_dy: usize) -> Vec<SlopeCount> {
+        let mut counts: Vec<_> = (1..=max_dy)
+            .flat_map(|dy| (0..=max_dx).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| gcd(dx, dy) == 1)
+            .map(|(dx, dy)| SlopeCount {
+                dx,
+                dy,
+                trees: self.check_slope(dx, dy),
+            })
+            .collect();
+        counts.sort_by_key(|count| (count.trees, count.dy, count.dx));
+        counts
+    }
+
+    /// The slope hitting the fewest trees, preferring the smallest `dy` and then `dx`.
+    pub fn fewest_trees(&self, max_dx: usize, max_dy: usize) -> Option<SlopeCount> {
+        self.sweep_slopes(max_dx, max_dy).first().copied()
+    }
+
+    /// The slope hitting the most trees, preferring the smallest `dy` and then `dx`.
+    pub fn most_trees(&self, max_dx: usize, max_dy: usize) -> Option<SlopeCount> {
+        let counts = self.sweep_slopes(max_dx, max_dy);
+        let most = counts.last()?.trees;
+        counts.into_iter().find(|count| count.trees == most)
+    }
+}
+
+impl Solution for Map {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Map::from_str(input)
+    }
+
+    fn part_1(&self) -> Answer {
+        self.check_slope(3, 1).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        slopes
+            .iter()
+            .fold(1, |product, slope| {
+                product * self.check_slope(slope.0, slope.1)
+            })
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_parts() {
+        let map = Map::parse(TEST_INPUT).unwrap();
+        assert_eq!(map.part_1(), Answer::Unsigned(7));
+        assert_eq!(map.part_2(), Answer::Unsigned(336));
+    }
+
+    #[test]
+    fn test_sweep_slopes() {
+        let map = Map::parse(TEST_INPUT).unwrap();
+        let counts = map.sweep_slopes(7, 2);
+        // dx 0..=7 for dy 1 and the odd dx for dy 2
+        assert_eq!(counts.len(), 8 + 4);
+        assert!(counts.windows(2).all(|w| w[0].trees <= w[1].trees));
+        assert!(!counts.iter().any(|count| (count.dx, count.dy) == (2, 2)));
+        assert!(counts.contains(&SlopeCount {
+            dx: 3,
+            dy: 1,
+            trees: 7
+        }));
+        assert!(counts.contains(&SlopeCount {
+            dx: 1,
+            dy: 2,
+            trees: 2
+        }));
+
+        let fewest = map.fewest_trees(7, 2).unwrap();
+        assert_eq!(fewest.trees, counts[0].trees);
+        let most = map.most_trees(7, 2).unwrap();
+        assert_eq!(most.trees, counts[counts.len() - 1].trees);
+        assert_eq!(map.fewest_trees(0, 0), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let map = Map::parse(TEST_INPUT).unwrap();
+        let rendering = map.render(3, 1);
+        let text = rendering.to_string();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "X.##.........##.........##.......");
+        assert_eq!(lines[1], "#..X#...#..#...#...#..#...#...#..");
+        assert_eq!(lines[3], "..#.#...#X#..#.#...#.#..#.#...#.#");
+        assert_eq!(text.matches('O').count(), map.check_slope(3, 1));
+
+        let svg = rendering.to_svg(10);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r#"width="330" height="110""#));
+        assert!(rendering.to_ansi().contains("\x1b[1;31mO\x1b[0m"));
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let map = Map::parse(TEST_INPUT).unwrap();
+        assert_eq!(map.path(3, 1).len(), 11);
+
+        let map = map.with_boundary(Boundary::Stop);
+        assert_eq!(map.path(3, 1), vec![(0, 0), (3, 1), (6, 2), (9, 3)]);
+        assert_eq!(map.check_slope(3, 1), 1);
+
+        let map = map.with_boundary(Boundary::Clamp);
+        let path = map.path(3, 1);
+        assert_eq!(path.len(), 11);
+        assert_eq!(path[4..6], [(10, 4), (10, 5)]);
+
+        // 11 columns and 11 rows with a step of (3, 1) return to the start after 11 steps, with
+        // (3, 2) after 11 steps as well since 2 and 11 are coprime
+        let map = map.with_boundary(Boundary::Toroidal);
+        assert_eq!(map.path(3, 1).len(), 11);
+        assert_eq!(map.path(3, 2).len(), 11);
+        assert_eq!(map.path(0, 11).len(), 1);
+        assert_eq!(map.path(3, 2)[6], (18, 12));
+        assert!(map.check_slope(3, 2) > 0);
+        let rendering = map.render(3, 2);
+        assert_eq!(rendering.squares.len(), 11);
+        assert_eq!(rendering.squares[0].len(), 11);
+        assert_eq!(rendering.squares[1][7], Square::Clear);
+        let text = rendering.to_string();
+        assert_eq!(text.matches(['X', 'O']).count(), 11);
+
+        assert_eq!("torus".parse::<Boundary>().unwrap_err().line, 1);
+        assert_eq!("clamp".parse::<Boundary>().unwrap(), Boundary::Clamp);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let input = "..#\n#.\n...";
+        let err = Map::parse(input).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected a row of 3 squares like the first row, found \"#.\""
+        );
+
+        let map = Map::parse_rows(input, RaggedRows::Pad).unwrap();
+        assert_eq!(map.width(), 3);
+        assert!(!map.check_position(2, 1));
+        assert_eq!(
+            Map::parse_rows("#\n..##", RaggedRows::Pad).unwrap().width(),
+            4
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use crate::{Boundary, Map};
+
+/// A square of a rendered map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Open,
+    Tree,
+    /// An open square on the path
+    Clear,
+    /// A tree on the path
+    Hit,
+}
+
+impl Square {
+    pub fn symbol(self) -> char {
+        match self {
+            Square::Open => '.',
+            Square::Tree => '#',
+            Square::Clear => 'X',
+            Square::Hit => 'O',
+        }
+    }
+
+    // ANSI escape sequence used for the square in a terminal
+    fn ansi_style(self) -> &'static str {
+        match self {
+            Square::Open => "\x1b[2m",
+            Square::Tree => "\x1b[32m",
+            Square::Clear => "\x1b[1;33m",
+            Square::Hit => "\x1b[1;31m",
+        }
+    }
+
+    fn svg_fill(self) -> &'static str {
+        match self {
+            Square::Open => "#ffffff",
+            Square::Tree => "#2e7d32",
+            Square::Clear => "#fbc02d",
+            Square::Hit => "#d32f2f",
+        }
+    }
+}
+
+/// The map repeated as far as a path goes, with the path drawn over it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendering {
+    pub squares: Vec<Vec<Square>>,
+}
+
+impl Map {
+    /// Draws the path going `dx` right and `dy` down per step over the map.
+    ///
+    /// A toroidal path is drawn on a single copy of the map, it would span up to width times
+    /// height copies otherwise.
+    pub fn render(&self, dx: usize, dy: usize) -> Rendering {
+        let mut path = self.path(dx, dy);
+        if self.boundary() == Boundary::Toroidal {
+            for (x, y) in &mut path {
+                *x %= self.width();
+                *y %= self.height();
+            }
+        }
+        // whole copies of the map as far as the path goes, at least one
+        let copies = |end: usize, size: usize| size * end.div_ceil(size.max(1)).max(1);
+        let columns = copies(
+            path.iter().map(|&(x, _)| x + 1).max().unwrap_or(0),
+            self.width(),
+        );
+        let rows = copies(
+            path.iter().map(|&(_, y)| y + 1).max().unwrap_or(0),
+            self.height(),
+        );
+
+        let mut squares: Vec<Vec<_>> = (0..rows)
+            .map(|y| {
+                (0..columns)
+                    .map(|x| {
+                        if self.check_position(x, y) {
+                            Square::Tree
+                        } else {
+                            Square::Open
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        for (x, y) in path {
+            let square = &mut squares[y][x];
+            *square = match square {
+                Square::Tree => Square::Hit,
+                _ => Square::Clear,
+            };
+        }
+        Rendering { squares }
+    }
+}
+
+impl Rendering {
+    /// The rendering with ANSI colors for a terminal.
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in &self.squares {
+            for square in row {
+                out.push_str(square.ansi_style());
+                out.push(square.symbol());
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The rendering as an SVG image with squares of `cell` pixels.
+    pub fn to_svg(&self, cell: usize) -> String {
+        let width = self.squares.first().map_or(0, Vec::len) * cell;
+        let height = self.squares.len() * cell;
+        let mut out = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            width, height, width, height
+        );
+        out.push('\n');
+        for (y, row) in self.squares.iter().enumerate() {
+            for (x, square) in row.iter().enumerate() {
+                out.push_str(&format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    x * cell,
+                    y * cell,
+                    cell,
+                    cell,
+                    square.svg_fill()
+                ));
+                out.push('\n');
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+impl fmt::Display for Rendering {
+    /// Plain text with `O` for trees and `X` for open squares on the path.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.squares {
+            let line: String = row.iter().map(|square| square.symbol()).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
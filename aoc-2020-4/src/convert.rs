@@ -0,0 +1,377 @@
+use std::io::{self, Write};
+
+use aoc_common::{parse_lines, ParseError};
+
+use crate::{Mode, Passport};
+
+/// The order of the fields in exports, fields which are not part of the puzzle follow sorted by
+/// key.
+pub const COLUMNS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+impl Passport {
+    /// The keys and values of all fields in export order.
+    pub fn fields(&self) -> Vec<(&str, String)> {
+        let mut keys: Vec<_> = self.fields.keys().map(String::as_str).collect();
+        keys.sort_unstable_by_key(|key| {
+            let column = COLUMNS.iter().position(|column| column == key);
+            (column.unwrap_or(COLUMNS.len()), *key)
+        });
+        keys.into_iter()
+            .map(|key| (key, self.fields[key].to_string()))
+            .collect()
+    }
+
+    /// Creates a passport from keys and values, an empty value is a field which is present but
+    /// empty like `byr:` in the batch format.
+    ///
+    /// The error is relative to the fields written in the batch format.
+    pub fn from_fields<'a>(
+        fields: impl IntoIterator<Item = (&'a str, &'a str)>,
+        mode: Mode,
+    ) -> Result<Passport, ParseError> {
+        let mut text = String::new();
+        for (key, value) in fields {
+            if key.is_empty() {
+                return Err(ParseError::new(key, key, "a key"));
+            }
+            if key.contains(':') {
+                return Err(ParseError::new(key, key, "a key without ':'"));
+            }
+            if let Some(c) = key.chars().chain(value.chars()).find(|c| c.is_whitespace()) {
+                return Err(ParseError::new(
+                    key,
+                    key,
+                    format!("fields without whitespace like {:?}", c),
+                ));
+            }
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(key);
+            text.push(':');
+            text.push_str(value);
+        }
+        Passport::parse(&text, mode)
+    }
+}
+
+fn imported(
+    input: &str,
+    line: &str,
+    fields: Vec<(&str, &str)>,
+    mode: Mode,
+) -> Result<Passport, ParseError> {
+    Passport::from_fields(fields, mode).map_err(|err| ParseError::new(input, line, err.expected))
+}
+
+/// Checks that no passport uses a key more than once, the exports hold only one value per key so
+/// such passports wouldn't survive a conversion. The error names the 1-based passport.
+///
+/// Use `check_csv_exportable` for CSV.
+pub fn check_exportable<'a>(
+    passports: impl IntoIterator<Item = &'a Passport>,
+) -> Result<(), String> {
+    for (i, passport) in passports.into_iter().enumerate() {
+        if let Some(key) = passport.duplicates().first() {
+            return Err(format!(
+                "passport {}: {} used more than once, only one value per key can be exported",
+                i + 1,
+                key
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Like `check_exportable`, but also fails for empty values which CSV can't tell apart from
+/// missing fields.
+pub fn check_csv_exportable(passports: &[Passport]) -> Result<(), String> {
+    check_exportable(passports)?;
+    for (i, passport) in passports.iter().enumerate() {
+        if let Some((key, _)) = passport
+            .fields()
+            .into_iter()
+            .find(|(_, value)| value.is_empty())
+        {
+            return Err(format!(
+                "passport {}: {} is empty, CSV can't tell it apart from a missing field",
+                i + 1,
+                key
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Writes passports in the batch format of the puzzle, one passport per line.
+pub fn write_batch<'a>(
+    mut writer: impl Write,
+    passports: impl IntoIterator<Item = &'a Passport>,
+) -> io::Result<()> {
+    for (i, passport) in passports.into_iter().enumerate() {
+        if i > 0 {
+            writeln!(writer)?;
+        }
+        let fields: Vec<_> = passport
+            .fields()
+            .into_iter()
+            .map(|(key, value)| format!("{}:{}", key, value))
+            .collect();
+        writeln!(writer, "{}", fields.join(" "))?;
+    }
+    Ok(())
+}
+
+/// Writes one JSON object with string values per passport and line.
+pub fn write_json_lines<'a>(
+    mut writer: impl Write,
+    passports: impl IntoIterator<Item = &'a Passport>,
+) -> io::Result<()> {
+    for passport in passports {
+        let fields: Vec<_> = passport
+            .fields()
+            .into_iter()
+            .map(|(key, value)| format!("{}:{}", json_string(key), json_string(&value)))
+            .collect();
+        writeln!(writer, "{{{}}}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+fn json_string(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
+/// Reads passports written by `write_json_lines`, blank lines are skipped.
+pub fn read_json_lines(input: &str, mode: Mode) -> Result<Vec<Passport>, ParseError> {
+    let passports = parse_lines(input, |line| {
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+        let object: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(line).map_err(|_| ParseError::new(line, line, "a JSON object"))?;
+        let fields = object
+            .iter()
+            .map(|(key, value)| match value.as_str() {
+                Some(value) => Ok((key.as_str(), value)),
+                None => Err(ParseError::new(
+                    line,
+                    line,
+                    format!("a string as value of {}", key),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        imported(line, line, fields, mode).map(Some)
+    })?;
+    Ok(passports.into_iter().flatten().collect())
+}
+
+/// Writes a CSV file with a header, the columns of `COLUMNS` and then every other key used in
+/// the passports. Missing fields are empty, see `check_csv_exportable`.
+pub fn write_csv(writer: impl Write, passports: &[Passport]) -> Result<(), csv::Error> {
+    let mut columns: Vec<&str> = COLUMNS.to_vec();
+    for passport in passports {
+        for (key, _) in passport.fields() {
+            if !columns.contains(&key) {
+                columns.push(key);
+            }
+        }
+    }
+    columns[COLUMNS.len()..].sort_unstable();
+
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(&columns)?;
+    for passport in passports {
+        writer.write_record(columns.iter().map(|key| {
+            passport
+                .get(key)
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        }))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// The text of the 1-based line, for errors
+fn line_of(input: &str, line: u64) -> &str {
+    input
+        .lines()
+        .nth(line as usize - 1)
+        .unwrap_or(&input[input.len()..])
+}
+
+/// Reads passports from a CSV file with a header row naming the fields, in any order. Empty cells
+/// are missing fields.
+pub fn read_csv(input: &str, mode: Mode) -> Result<Vec<Passport>, ParseError> {
+    let mut reader = csv::Reader::from_reader(input.as_bytes());
+    let csv_error = |err: csv::Error| {
+        let line = err.position().map_or(1, |position| position.line());
+        ParseError::new(
+            input,
+            line_of(input, line),
+            "a CSV row with a value per column",
+        )
+    };
+    let header = reader.headers().map_err(csv_error)?.clone();
+
+    let mut passports = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let line = line_of(
+            input,
+            record.position().map_or(1, |position| position.line()),
+        );
+        let fields = header
+            .iter()
+            .zip(record.iter())
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+        passports.push(imported(input, line, fields, mode)?);
+    }
+    Ok(passports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Batch, FieldContent};
+
+    const TEST_INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929 nat:ch";
+
+    fn passports() -> Vec<Passport> {
+        Batch::parse_with(TEST_INPUT, Mode::Lenient)
+            .unwrap()
+            .passports
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let mut out = Vec::new();
+        write_batch(&mut out, &passports()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:860033327 cid:147\n\n\
+             byr:1929 iyr:2013 eyr:2023 hcl:#cfa07d ecl:amb pid:028048884 cid:350 nat:ch\n"
+        );
+        assert_eq!(
+            Batch::parse_with(&out, Mode::Lenient).unwrap().passports,
+            passports()
+        );
+    }
+
+    #[test]
+    fn test_json_lines_round_trip() {
+        let mut out = Vec::new();
+        write_json_lines(&mut out, &passports()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("{\"byr\":\"1937\",\"iyr\":\"2017\","));
+        assert_eq!(read_json_lines(&out, Mode::Lenient).unwrap(), passports());
+
+        let err = read_json_lines(&out, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.expected.starts_with("one of")), (2, true));
+        let err = read_json_lines("{\"byr\": 1937}", Mode::Strict).unwrap_err();
+        assert_eq!(err.expected, "a string as value of byr");
+        let err = read_json_lines("{\"hgt\": \"1 m\"}", Mode::Strict).unwrap_err();
+        assert_eq!(err.expected, "fields without whitespace like ' '");
+        let err = read_json_lines("{\"a:b\": \"c\"}", Mode::Lenient).unwrap_err();
+        assert_eq!(err.expected, "a key without ':'");
+        let err = read_json_lines("{\"\": \"c\"}", Mode::Lenient).unwrap_err();
+        assert_eq!(err.expected, "a key");
+    }
+
+    #[test]
+    fn test_values_as_written() {
+        let input = "byr:0937 cid:007 pid:000000001\n\neyr:2020 foo:01";
+        let passports = Batch::parse_with(input, Mode::Lenient)
+            .unwrap()
+            .into_passports();
+        assert_eq!(check_exportable(&passports), Ok(()));
+
+        let mut out = Vec::new();
+        write_batch(&mut out, &passports).unwrap();
+        let batch = String::from_utf8(out).unwrap();
+        assert!(batch.starts_with("byr:0937 pid:000000001 cid:007\n"));
+        assert_eq!(
+            Batch::parse_with(&batch, Mode::Lenient).unwrap().passports,
+            passports
+        );
+
+        let mut out = Vec::new();
+        write_json_lines(&mut out, &passports).unwrap();
+        let json = String::from_utf8(out).unwrap();
+        assert!(json.contains("\"cid\":\"007\""));
+        assert_eq!(read_json_lines(&json, Mode::Lenient).unwrap(), passports);
+
+        let mut out = Vec::new();
+        write_csv(&mut out, &passports).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        assert_eq!(csv.lines().nth(2), Some(",,2020,,,,,,01"));
+        assert_eq!(read_csv(&csv, Mode::Lenient).unwrap(), passports);
+    }
+
+    #[test]
+    fn test_empty_values() {
+        let passports = Batch::parse_with("byr: iyr:2010", Mode::Strict)
+            .unwrap()
+            .into_passports();
+        assert_eq!(
+            passports[0].get("byr"),
+            Some(&FieldContent::BirthYear("".into()))
+        );
+
+        let mut out = Vec::new();
+        write_json_lines(&mut out, &passports).unwrap();
+        let json = String::from_utf8(out).unwrap();
+        assert_eq!(json, "{\"byr\":\"\",\"iyr\":\"2010\"}\n");
+        assert_eq!(read_json_lines(&json, Mode::Strict).unwrap(), passports);
+
+        assert_eq!(check_exportable(&passports), Ok(()));
+        assert_eq!(
+            check_csv_exportable(&passports),
+            Err("passport 1: byr is empty, CSV can't tell it apart from a missing field".into())
+        );
+    }
+
+    #[test]
+    fn test_duplicates_not_exportable() {
+        let input = "byr:1937\n\nbyr:1937 foo:1 foo:2";
+        let passports = Batch::parse_with(input, Mode::Lenient)
+            .unwrap()
+            .into_passports();
+        assert_eq!(
+            check_exportable(&passports),
+            Err(
+                "passport 2: foo used more than once, only one value per key can be exported"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let mut out = Vec::new();
+        write_csv(&mut out, &passports()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "byr,iyr,eyr,hgt,hcl,ecl,pid,cid,nat");
+        assert_eq!(lines[2], "1929,2013,2023,,#cfa07d,amb,028048884,350,ch");
+        let imported = read_csv(&out, Mode::Lenient).unwrap();
+        assert_eq!(imported, passports());
+        assert_eq!(imported[0].get("nat"), None);
+        assert_eq!(
+            imported[1].get("pid"),
+            Some(&FieldContent::PassportID("028048884".into()))
+        );
+
+        let err = read_csv("byr,iyr\n1937,2017\n1929\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (3, "1929"));
+        let err = read_csv("byr,foo\n1937,bar\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (2, "1937,bar"));
+    }
+}
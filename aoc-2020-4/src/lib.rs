@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{Answer, ParseError, Solution};
+use lazy_static::lazy_static;
+
+mod convert;
+mod reader;
+mod schema;
+
+pub use convert::{
+    check_csv_exportable, check_exportable, read_csv, read_json_lines, write_batch, write_csv,
+    write_json_lines, COLUMNS,
+};
+pub use reader::{ReadError, Records};
+pub use schema::{Bounds, FieldRule, FieldType, Schema, SchemaError};
+
+lazy_static! {
+    static ref PUZZLE_SCHEMA: Schema = Schema::default();
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FieldContent {
+    BirthYear(String),
+    IssueYear(String),
+    ExpirationYear(String),
+    Height(String),
+    HairColor(String),
+    EyeColor(String),
+    // pid can also contain non numeric values
+    PassportID(String),
+    CountryID(String),
+    /// The value of a field which is not part of the puzzle, only kept in lenient mode
+    Unknown(String),
+}
+
+impl fmt::Display for FieldContent {
+    /// The value as written in the passport, it is checked against the schema.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldContent::BirthYear(value)
+            | FieldContent::IssueYear(value)
+            | FieldContent::ExpirationYear(value)
+            | FieldContent::CountryID(value)
+            | FieldContent::Height(value)
+            | FieldContent::HairColor(value)
+            | FieldContent::EyeColor(value)
+            | FieldContent::PassportID(value)
+            | FieldContent::Unknown(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// How fields which are not part of the puzzle are handled when parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Unknown fields and keys used twice are an error
+    Strict,
+    /// Unknown fields are kept as `FieldContent::Unknown`, for keys used twice the first value is
+    /// kept and the duplicate is reported by `Passport::validate`
+    Lenient,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Field(String, FieldContent);
+
+impl FromStr for Field {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Field::parse(s, Mode::Strict)
+    }
+}
+
+impl Field {
+    fn parse(s: &str, mode: Mode) -> Result<Self, ParseError> {
+        let mut field = s.splitn(2, ':');
+        let key = field.next().unwrap_or_default();
+        let value = field.next().ok_or_else(|| ParseError::missing(s, "':'"))?;
+        let content = match key {
+            // values are kept as written, the schema decides whether they are valid
+            "byr" => FieldContent::BirthYear(value.into()),
+            "iyr" => FieldContent::IssueYear(value.into()),
+            "eyr" => FieldContent::ExpirationYear(value.into()),
+            "hgt" => FieldContent::Height(value.into()),
+            "hcl" => FieldContent::HairColor(value.into()),
+            "ecl" => FieldContent::EyeColor(value.into()),
+            "pid" => FieldContent::PassportID(value.into()),
+            "cid" => FieldContent::CountryID(value.into()),
+            _ if mode == Mode::Lenient && !key.is_empty() => FieldContent::Unknown(value.into()),
+            _ => {
+                return Err(ParseError::new(
+                    s,
+                    key,
+                    "one of byr, iyr, eyr, hgt, hcl, ecl, pid or cid",
+                ))
+            }
+        };
+        Ok(Field(key.into(), content))
+    }
+}
+
+/// A problem with one field of a passport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    Missing(String),
+    Duplicate(String),
+    Invalid { key: String, reason: String },
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::Missing(key) => write!(f, "{}: missing", key),
+            FieldError::Duplicate(key) => write!(f, "{}: used more than once", key),
+            FieldError::Invalid { key, reason } => write!(f, "{}: {}", key, reason),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Passport {
+    fields: HashMap<String, FieldContent>,
+    // keys used more than once, only in lenient mode
+    duplicates: Vec<String>,
+}
+
+impl FromStr for Passport {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Passport::parse(s, Mode::Strict)
+    }
+}
+
+impl Passport {
+    /// Parses the whitespace separated fields of a passport.
+    pub fn parse(s: &str, mode: Mode) -> Result<Passport, ParseError> {
+        let mut fields = HashMap::<String, FieldContent>::new();
+        let mut duplicates = Vec::new();
+
+        for token in s.split_whitespace() {
+            let field = Field::parse(token, mode).map_err(|err| err.within(s, token))?;
+            if fields.contains_key(&field.0) {
+                let key = &token[..field.0.len()];
+                match mode {
+                    Mode::Strict => {
+                        return Err(ParseError::new(s, key, format!("{} only once", key)))
+                    }
+                    Mode::Lenient if !duplicates.contains(&field.0) => duplicates.push(field.0),
+                    Mode::Lenient => {}
+                }
+                continue;
+            }
+            fields.insert(field.0, field.1);
+        }
+
+        Ok(Passport { fields, duplicates })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&FieldContent> {
+        self.fields.get(key)
+    }
+
+    /// Keys used more than once, only their first value is kept.
+    pub fn duplicates(&self) -> &[String] {
+        &self.duplicates
+    }
+
+    /// Whether all fields required by the schema are present (part 1).
+    pub fn has_required_fields(&self, schema: &Schema) -> bool {
+        schema.required().all(|key| self.fields.contains_key(key))
+    }
+
+    /// Whether all required fields are present and all fields are valid (part 2).
+    pub fn is_valid(&self, schema: &Schema) -> bool {
+        self.validate(schema).is_ok()
+    }
+
+    /// Checks the passport against the schema, the error lists every missing field, every key
+    /// used more than once and every invalid field, each ordered by key.
+    pub fn validate(&self, schema: &Schema) -> Result<(), Vec<FieldError>> {
+        let mut errors: Vec<_> = schema
+            .required()
+            .filter(|key| !self.fields.contains_key(*key))
+            .map(|key| FieldError::Missing(key.into()))
+            .collect();
+
+        let mut duplicates = self.duplicates.clone();
+        duplicates.sort_unstable();
+        errors.extend(duplicates.into_iter().map(FieldError::Duplicate));
+
+        let mut keys: Vec<_> = self.fields.keys().collect();
+        keys.sort_unstable();
+        for key in keys {
+            if let Err(reason) = schema.check_field(key, &self.fields[key].to_string()) {
+                errors.push(FieldError::Invalid {
+                    key: key.clone(),
+                    reason,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+pub struct Batch {
+    passports: Vec<Passport>,
+}
+
+impl Batch {
+    /// Parses a whole batch, see `Records` for the format.
+    pub fn parse_with(input: &str, mode: Mode) -> Result<Batch, ParseError> {
+        let passports = Records::new(input.as_bytes(), mode)
+            .map(|record| match record {
+                Ok(passport) => Ok(passport),
+                Err(ReadError::Parse(err)) => Err(err),
+                Err(ReadError::Io(err)) => unreachable!("reading a str failed: {}", err),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Batch { passports })
+    }
+
+    pub fn passports(&self) -> &[Passport] {
+        &self.passports
+    }
+
+    pub fn into_passports(self) -> Vec<Passport> {
+        self.passports
+    }
+
+    pub fn count_valid(&self, schema: &Schema) -> usize {
+        self.passports
+            .iter()
+            .filter(|passport| passport.is_valid(schema))
+            .count()
+    }
+}
+
+impl Solution for Batch {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Batch::parse_with(input, Mode::Strict)
+    }
+
+    fn part_1(&self) -> Answer {
+        self.passports
+            .iter()
+            .filter(|passport| passport.has_required_fields(&PUZZLE_SCHEMA))
+            .count()
+            .into()
+    }
+
+    fn part_2(&self) -> Answer {
+        self.count_valid(&PUZZLE_SCHEMA).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_from_str() {
+        let input = "ecl:gry";
+        assert_eq!(
+            Field::from_str(input),
+            Ok(Field("ecl".into(), FieldContent::EyeColor("gry".into())))
+        );
+    }
+
+    #[test]
+    fn test_hcl_valid() {
+        let input = "hcl:#602927";
+        let field = Field::from_str(input).unwrap();
+        assert_eq!(
+            Schema::default().check_field(&field.0, &field.1.to_string()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_hgt_valid() {
+        let input = "hgt:183cm";
+        let field = Field::from_str(input).unwrap();
+        assert_eq!(
+            Schema::default().check_field(&field.0, &field.1.to_string()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_invalid_passports_part_2() {
+        let inputs = [
+            "eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926",
+            "iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946",
+            "hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277",
+            "hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007",
+        ];
+        for input in &inputs {
+            let passport = Passport::from_str(input).unwrap();
+            assert!(!passport.is_valid(&PUZZLE_SCHEMA));
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "ecl:gry byr:1937\n\niyr:2013 ecl:amb\nhgt:179cm foo:bar";
+        let err = Batch::parse(input).err().unwrap();
+        assert_eq!((err.line, err.column), (4, 11));
+        assert_eq!(err.text, "foo");
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = Schema::default();
+        assert_eq!(
+            schema.check_field("hgt", "59cm"),
+            Err("59cm below minimum 150cm".into())
+        );
+        assert_eq!(
+            schema.check_field("hgt", "190"),
+            Err("190 has none of the units cm,in".into())
+        );
+        assert_eq!(
+            schema.check_field("byr", "2003"),
+            Err("2003 above maximum 2002".into())
+        );
+        assert_eq!(
+            schema.check_field("ecl", "zzz"),
+            Err("zzz not one of amb,blu,brn,gry,grn,hzl,oth".into())
+        );
+        assert_eq!(schema.check_field("cid", "anything"), Ok(()));
+        assert_eq!(schema.required().count(), 7);
+
+        let json = r#"{"fields": {
+            "ecl": {"required": true, "one_of": ["amb", "blu", "brn", "gry", "grn", "hzl", "oth", "vio"]},
+            "iyr": {"type": "integer", "min": 2015}
+        }}"#;
+        let schema = Schema::from_json(json).unwrap();
+        assert_eq!(schema.check_field("ecl", "vio"), Ok(()));
+        assert_eq!(
+            schema.check_field("iyr", "2012").unwrap_err(),
+            "2012 below minimum 2015"
+        );
+        assert_eq!(
+            schema.check_field("iyr", "soon").unwrap_err(),
+            "soon is not an integer"
+        );
+        let passport = Passport::from_str("ecl:vio iyr:2016").unwrap();
+        assert!(passport.is_valid(&schema));
+        let passport = Passport::from_str("ecl:vio iyr:20x6").unwrap();
+        assert_eq!(
+            passport.validate(&schema),
+            Err(vec![FieldError::Invalid {
+                key: "iyr".into(),
+                reason: "20x6 is not an integer".into()
+            }])
+        );
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let err = Schema::from_toml("[fields.hgt]\ntype = \"measure\"").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "field hgt: measures need at least one unit"
+        );
+        let err = Schema::from_toml("[fields.hcl]\npattern = \"[\"").unwrap_err();
+        assert!(err.to_string().starts_with("field hcl: invalid pattern"));
+        assert!(Schema::from_toml("[fields.byr]\nrange = 1").is_err());
+        assert!(Schema::from_json("{\"fields\": {\"byr\": {\"type\": \"date\"}}}").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let passport =
+            Passport::from_str("hgt:59cm ecl:zzz\neyr:2038 hcl:74454a iyr:2023").unwrap();
+        let errors = passport.validate(&PUZZLE_SCHEMA).unwrap_err();
+        let errors: Vec<_> = errors.iter().map(|err| err.to_string()).collect();
+        assert_eq!(
+            errors,
+            [
+                "byr: missing",
+                "pid: missing",
+                "ecl: zzz not one of amb,blu,brn,gry,grn,hzl,oth",
+                "eyr: 2038 above maximum 2030",
+                "hcl: 74454a does not match ^#[0-9a-f]{6}$",
+                "hgt: 59cm below minimum 150cm",
+                "iyr: 2023 above maximum 2020",
+            ]
+        );
+
+        let passport = Passport::from_str(
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f",
+        )
+        .unwrap();
+        assert_eq!(passport.validate(&PUZZLE_SCHEMA), Ok(()));
+    }
+
+    #[test]
+    fn test_unknown_and_duplicate_fields() {
+        let input = "ecl:gry byr:1937\n\niyr:2013 ecl:amb\nhgt:179cm foo:bar";
+        let batch = Batch::parse_with(input, Mode::Lenient).unwrap();
+        let passport = &batch.passports()[1];
+        assert_eq!(
+            passport.get("foo"),
+            Some(&FieldContent::Unknown("bar".into()))
+        );
+        assert_eq!(
+            passport.get("hgt"),
+            Some(&FieldContent::Height("179cm".into()))
+        );
+
+        let input = "ecl:gry byr:1937\npid:1 byr:1940";
+        let err = Batch::parse_with(input, Mode::Strict).err().unwrap();
+        assert_eq!((err.line, err.column), (2, 7));
+        assert_eq!(err.text, "byr");
+
+        let passport = Passport::parse(input, Mode::Lenient).unwrap();
+        assert_eq!(
+            passport.get("byr"),
+            Some(&FieldContent::BirthYear("1937".into()))
+        );
+        let errors = passport.validate(&PUZZLE_SCHEMA).unwrap_err();
+        assert!(errors.contains(&FieldError::Duplicate("byr".into())));
+        assert!(Passport::parse(":x", Mode::Lenient).is_err());
+    }
+}
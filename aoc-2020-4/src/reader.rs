@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use aoc_common::ParseError;
+
+use crate::{Mode, Passport};
+
+/// An error while reading a batch file.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "{}", err),
+            ReadError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+/// Reads the passports of a batch file one at a time.
+///
+/// Records are separated by blank lines, lines may end with `\r\n` and trailing whitespace is
+/// ignored. Positions of parse errors are relative to the whole file, reading continues with the
+/// next record after them and only stops after an I/O error.
+pub struct Records<R> {
+    reader: R,
+    mode: Mode,
+    // number of lines read so far
+    line: usize,
+    buffer: String,
+    done: bool,
+}
+
+impl<R: BufRead> Records<R> {
+    pub fn new(reader: R, mode: Mode) -> Records<R> {
+        Records {
+            reader,
+            mode,
+            line: 0,
+            buffer: String::new(),
+            done: false,
+        }
+    }
+
+    // The lines of the next record and the number of its first line
+    fn next_record(&mut self) -> io::Result<Option<(String, usize)>> {
+        let mut record = String::new();
+        let mut start = 0;
+        loop {
+            self.buffer.clear();
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Ok(if record.is_empty() {
+                    None
+                } else {
+                    Some((record, start))
+                });
+            }
+            self.line += 1;
+
+            let line = self.buffer.trim_end();
+            if line.trim_start().is_empty() {
+                if !record.is_empty() {
+                    return Ok(Some((record, start)));
+                }
+                continue;
+            }
+            if record.is_empty() {
+                start = self.line;
+            } else {
+                record.push('\n');
+            }
+            record.push_str(line);
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<Passport, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = match self.next_record() {
+            Ok(Some((record, start))) => Passport::parse(&record, self.mode).map_err(|mut err| {
+                err.line += start - 1;
+                ReadError::Parse(err)
+            }),
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(err) => Err(ReadError::Io(err)),
+        };
+        // records are split on blank lines so a bad record doesn't affect the following ones
+        self.done = matches!(result, Err(ReadError::Io(_)));
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FieldContent;
+
+    #[test]
+    fn test_records() {
+        let input = "ecl:gry  \r\nbyr:1937\r\n\r\n\r\n  \r\niyr:2013 ecl:amb\r\nhgt:179cm";
+        let passports: Vec<_> = Records::new(input.as_bytes(), Mode::Strict)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(passports.len(), 2);
+        assert_eq!(
+            passports[0].get("byr"),
+            Some(&FieldContent::BirthYear("1937".into()))
+        );
+        assert_eq!(
+            passports[1].get("hgt"),
+            Some(&FieldContent::Height("179cm".into()))
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let input = "ecl:gry byr:1937\n\n\niyr:2013 ecl:amb\nhgt:179cm foo:bar\n\nbyr\n\nhgt:1in";
+        let mut records = Records::new(input.as_bytes(), Mode::Strict);
+        assert!(records.next().unwrap().is_ok());
+        match records.next() {
+            Some(Err(ReadError::Parse(err))) => {
+                assert_eq!((err.line, err.column), (5, 11));
+                assert_eq!(err.text, "foo");
+            }
+            _ => panic!("expected a parse error"),
+        }
+        // the following records are still read
+        match records.next() {
+            Some(Err(ReadError::Parse(err))) => assert_eq!((err.line, err.column), (7, 4)),
+            _ => panic!("expected a parse error"),
+        }
+        assert!(records.next().unwrap().is_ok());
+        assert!(records.next().is_none());
+    }
+}
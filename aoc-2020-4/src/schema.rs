@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use regex::Regex;
+use serde::Deserialize;
+
+/// The type a field value has to have.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    #[default]
+    String,
+    Integer,
+    /// A number followed by a unit like `183cm`
+    Measure,
+}
+
+/// An inclusive range, either end can be open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bounds {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl Bounds {
+    fn check(&self, value: i64, unit: &str) -> Result<(), String> {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => {
+                Err(format!("{}{} below minimum {}{}", value, unit, min, unit))
+            }
+            (_, Some(max)) if value > max => {
+                Err(format!("{}{} above maximum {}{}", value, unit, max, unit))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// A field as written in the schema file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldSpec {
+    #[serde(default)]
+    required: bool,
+    #[serde(default, rename = "type")]
+    kind: FieldType,
+    min: Option<i64>,
+    max: Option<i64>,
+    pattern: Option<String>,
+    one_of: Option<Vec<String>>,
+    #[serde(default)]
+    units: BTreeMap<String, Bounds>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaSpec {
+    fields: BTreeMap<String, FieldSpec>,
+}
+
+/// The rules for the value of one field.
+#[derive(Debug, Clone)]
+pub struct FieldRule {
+    pub required: bool,
+    pub kind: FieldType,
+    /// Range of integers
+    pub bounds: Bounds,
+    pub pattern: Option<Regex>,
+    pub one_of: Option<Vec<String>>,
+    /// Range per unit of measures
+    pub units: BTreeMap<String, Bounds>,
+}
+
+impl FieldRule {
+    fn compile(spec: FieldSpec) -> Result<FieldRule, String> {
+        let bounds = Bounds {
+            min: spec.min,
+            max: spec.max,
+        };
+        if bounds != Bounds::default() && spec.kind != FieldType::Integer {
+            return Err("min and max are only allowed for integers".into());
+        }
+        match (spec.kind, spec.units.is_empty()) {
+            (FieldType::Measure, true) => return Err("measures need at least one unit".into()),
+            (FieldType::String, false) | (FieldType::Integer, false) => {
+                return Err("units are only allowed for measures".into())
+            }
+            _ => {}
+        }
+        let pattern = match spec.pattern {
+            Some(pattern) => {
+                Some(Regex::new(&pattern).map_err(|err| format!("invalid pattern: {}", err))?)
+            }
+            None => None,
+        };
+        Ok(FieldRule {
+            required: spec.required,
+            kind: spec.kind,
+            bounds,
+            pattern,
+            one_of: spec.one_of,
+            units: spec.units,
+        })
+    }
+
+    /// Checks a value, the error describes why it is invalid.
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self.kind {
+            FieldType::String => {}
+            FieldType::Integer => {
+                let number = value
+                    .parse()
+                    .map_err(|_| format!("{} is not an integer", value))?;
+                self.bounds.check(number, "")?;
+            }
+            FieldType::Measure => {
+                let units = || self.units.keys().cloned().collect::<Vec<_>>().join(",");
+                let split = value
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(value.len());
+                let (number, unit) = value.split_at(split);
+                let number = number.parse().map_err(|_| {
+                    format!(
+                        "{} is not a number with one of the units {}",
+                        value,
+                        units()
+                    )
+                })?;
+                let bounds = self
+                    .units
+                    .get(unit)
+                    .ok_or_else(|| format!("{} has none of the units {}", value, units()))?;
+                bounds.check(number, unit)?;
+            }
+        }
+        if let Some(one_of) = &self.one_of {
+            if !one_of.iter().any(|allowed| allowed == value) {
+                return Err(format!("{} not one of {}", value, one_of.join(",")));
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(value) {
+                return Err(format!("{} does not match {}", value, pattern));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error in a schema file.
+#[derive(Debug)]
+pub enum SchemaError {
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    Field { key: String, reason: String },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Toml(err) => write!(f, "{}", err),
+            SchemaError::Json(err) => write!(f, "{}", err),
+            SchemaError::Field { key, reason } => write!(f, "field {}: {}", key, reason),
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+/// The passport fields with their rules, loaded from TOML or JSON.
+///
+/// ```toml
+/// [fields.hgt]
+/// required = true
+/// type = "measure"
+/// units.cm = { min = 150, max = 193 }
+/// units.in = { min = 59, max = 76 }
+/// ```
+///
+/// The default is the schema of part 2, see `schema.toml`.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    fields: BTreeMap<String, FieldRule>,
+}
+
+impl Schema {
+    pub fn from_toml(s: &str) -> Result<Schema, SchemaError> {
+        Schema::compile(toml::from_str(s).map_err(SchemaError::Toml)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Schema, SchemaError> {
+        Schema::compile(serde_json::from_str(s).map_err(SchemaError::Json)?)
+    }
+
+    fn compile(spec: SchemaSpec) -> Result<Schema, SchemaError> {
+        let fields = spec
+            .fields
+            .into_iter()
+            .map(|(key, spec)| match FieldRule::compile(spec) {
+                Ok(rule) => Ok((key, rule)),
+                Err(reason) => Err(SchemaError::Field { key, reason }),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Schema { fields })
+    }
+
+    pub fn field(&self, key: &str) -> Option<&FieldRule> {
+        self.fields.get(key)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &FieldRule)> {
+        self.fields.iter().map(|(key, rule)| (key.as_str(), rule))
+    }
+
+    /// The keys every passport needs.
+    pub fn required(&self) -> impl Iterator<Item = &str> {
+        self.fields()
+            .filter(|(_, rule)| rule.required)
+            .map(|(key, _)| key)
+    }
+
+    /// Checks the value of a field, fields without a rule are always valid.
+    pub fn check_field(&self, key: &str, value: &str) -> Result<(), String> {
+        match self.fields.get(key) {
+            Some(rule) => rule.check(value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Schema {
+        Schema::from_toml(include_str!("../schema.toml")).expect("the built-in schema is valid")
+    }
+}
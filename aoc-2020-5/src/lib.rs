@@ -0,0 +1,401 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc_common::{parse_lines, Answer, ParseError, Solution};
+
+/// An invalid plane geometry or a seat outside of the plane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeatError {
+    NotPowerOfTwo(&'static str, u32),
+    TooManySeats {
+        rows: u32,
+        columns: u32,
+    },
+    OutOfRange {
+        row: u32,
+        column: u32,
+        geometry: Geometry,
+    },
+}
+
+impl fmt::Display for SeatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeatError::NotPowerOfTwo(name, value) => {
+                write!(f, "{} has to be a power of two, got {}", name, value)
+            }
+            SeatError::TooManySeats { rows, columns } => write!(
+                f,
+                "{} rows with {} columns have too many seats for 32 bit seat IDs",
+                rows, columns
+            ),
+            SeatError::OutOfRange {
+                row,
+                column,
+                geometry,
+            } => write!(
+                f,
+                "seat in row {} and column {} is outside of a plane with {} rows and {} columns",
+                row, column, geometry.rows, geometry.columns
+            ),
+        }
+    }
+}
+
+impl Error for SeatError {}
+
+/// The number of rows and columns of a plane, both are powers of two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Geometry {
+    rows: u32,
+    columns: u32,
+}
+
+impl Geometry {
+    /// The plane of the puzzle with 128 rows and 8 columns.
+    pub const PUZZLE: Geometry = Geometry {
+        rows: 128,
+        columns: 8,
+    };
+
+    pub fn new(rows: u32, columns: u32) -> Result<Geometry, SeatError> {
+        if !rows.is_power_of_two() {
+            return Err(SeatError::NotPowerOfTwo("rows", rows));
+        }
+        if !columns.is_power_of_two() {
+            return Err(SeatError::NotPowerOfTwo("columns", columns));
+        }
+        let geometry = Geometry { rows, columns };
+        if geometry.row_bits() + geometry.column_bits() > 32 {
+            return Err(SeatError::TooManySeats { rows, columns });
+        }
+        Ok(geometry)
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    /// The number of `F`/`B` characters of a seat code.
+    pub fn row_bits(&self) -> usize {
+        self.rows.trailing_zeros() as usize
+    }
+
+    /// The number of `L`/`R` characters of a seat code.
+    pub fn column_bits(&self) -> usize {
+        self.columns.trailing_zeros() as usize
+    }
+
+    /// The length of a seat code.
+    pub fn code_len(&self) -> usize {
+        self.row_bits() + self.column_bits()
+    }
+}
+
+impl Default for Geometry {
+    fn default() -> Geometry {
+        Geometry::PUZZLE
+    }
+}
+
+/// A seat given by its binary space partitioning code like `FBFBBFFRLR`.
+///
+/// The first characters halve the rows, `F` keeps the front and `B` the back half, the remaining
+/// ones halve the columns with `L` for the left and `R` for the right half.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardingPass {
+    row: u32,
+    column: u32,
+    geometry: Geometry,
+}
+
+impl BoardingPass {
+    pub fn new(geometry: Geometry, row: u32, column: u32) -> Result<BoardingPass, SeatError> {
+        if row >= geometry.rows || column >= geometry.columns {
+            return Err(SeatError::OutOfRange {
+                row,
+                column,
+                geometry,
+            });
+        }
+        Ok(BoardingPass {
+            row,
+            column,
+            geometry,
+        })
+    }
+
+    /// The seat with the given ID, the ID has to be within the plane.
+    pub fn from_seat_id(geometry: Geometry, seat_id: u32) -> Result<BoardingPass, SeatError> {
+        BoardingPass::new(
+            geometry,
+            seat_id / geometry.columns,
+            seat_id % geometry.columns,
+        )
+    }
+
+    pub fn decode(code: &str, geometry: Geometry) -> Result<BoardingPass, ParseError> {
+        if code.chars().count() != geometry.code_len() {
+            return Err(ParseError::new(
+                code,
+                code,
+                format!("a seat code of {} characters", geometry.code_len()),
+            ));
+        }
+
+        let mut row = 0;
+        let mut column = 0;
+        for (i, c) in code.char_indices() {
+            match (i < geometry.row_bits(), c) {
+                (true, 'F') => row <<= 1,
+                (true, 'B') => row = row << 1 | 1,
+                (false, 'L') => column <<= 1,
+                (false, 'R') => column = column << 1 | 1,
+                (in_row, _) => {
+                    let expected = if in_row { "'F' or 'B'" } else { "'L' or 'R'" };
+                    return Err(ParseError::new(code, &code[i..i + c.len_utf8()], expected));
+                }
+            }
+        }
+        Ok(BoardingPass {
+            row,
+            column,
+            geometry,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        let bits = |value: u32, count: usize, zero: char, one: char| {
+            (0..count)
+                .rev()
+                .map(move |bit| if value >> bit & 1 == 1 { one } else { zero })
+        };
+        bits(self.row, self.geometry.row_bits(), 'F', 'B')
+            .chain(bits(self.column, self.geometry.column_bits(), 'L', 'R'))
+            .collect()
+    }
+
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    /// The row times the number of columns plus the column.
+    pub fn seat_id(&self) -> u32 {
+        self.row * self.geometry.columns + self.column
+    }
+}
+
+impl FromStr for BoardingPass {
+    type Err = ParseError;
+
+    /// Decodes a seat code of the puzzle's plane.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BoardingPass::decode(s, Geometry::PUZZLE)
+    }
+}
+
+impl fmt::Display for BoardingPass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+/// Where a missing seat is relative to the occupied seats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Missing {
+    /// In front of the first occupied seat, these rows don't exist on the puzzle's plane
+    Front,
+    /// Between occupied seats
+    Interior,
+    /// Behind the last occupied seat
+    Back,
+}
+
+/// A seat without a boarding pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingSeat {
+    pub pass: BoardingPass,
+    pub missing: Missing,
+}
+
+pub struct Seats {
+    geometry: Geometry,
+    seat_ids: Vec<u32>,
+}
+
+impl Seats {
+    pub fn parse_with(input: &str, geometry: Geometry) -> Result<Seats, ParseError> {
+        let mut seat_ids = parse_lines(input, |line| {
+            BoardingPass::decode(line, geometry).map(|pass| pass.seat_id())
+        })?;
+        seat_ids.sort_unstable();
+        seat_ids.dedup();
+        Ok(Seats { geometry, seat_ids })
+    }
+
+    // Number of seats of the plane, fits into a u64 even for a plane using all 32 bits
+    fn seat_count(&self) -> u64 {
+        u64::from(self.geometry.rows) * u64::from(self.geometry.columns)
+    }
+
+    fn classify(&self, seat_id: u32) -> Option<Missing> {
+        match (self.seat_ids.first(), self.seat_ids.last()) {
+            _ if self.seat_ids.binary_search(&seat_id).is_ok() => None,
+            (Some(&first), _) if seat_id < first => Some(Missing::Front),
+            (_, Some(&last)) if seat_id > last => Some(Missing::Back),
+            (Some(_), Some(_)) => Some(Missing::Interior),
+            // without any boarding passes all seats count as front
+            _ => Some(Missing::Front),
+        }
+    }
+
+    /// Every seat of the plane without a boarding pass, ordered by seat ID.
+    pub fn missing(&self) -> Vec<MissingSeat> {
+        (0..self.seat_count())
+            .map(|seat_id| seat_id as u32)
+            .filter_map(|seat_id| {
+                let missing = self.classify(seat_id)?;
+                let pass = BoardingPass::from_seat_id(self.geometry, seat_id)
+                    .expect("seat IDs below the seat count are on the plane");
+                Some(MissingSeat { pass, missing })
+            })
+            .collect()
+    }
+
+    /// The runs of consecutive seat IDs missing between occupied seats.
+    pub fn gaps(&self) -> Vec<RangeInclusive<u32>> {
+        self.seat_ids
+            .windows(2)
+            .filter(|pair| pair[1] - pair[0] > 1)
+            .map(|pair| pair[0] + 1..=pair[1] - 1)
+            .collect()
+    }
+
+    /// One line per row with `#` for occupied seats, `O` for interior gaps and `.` for missing
+    /// seats in front and behind.
+    pub fn render(&self) -> String {
+        let width = self.geometry.rows.saturating_sub(1).to_string().len();
+        let mut out = String::new();
+        for row in 0..self.geometry.rows {
+            out.push_str(&format!("{:>width$} ", row, width = width));
+            for column in 0..self.geometry.columns {
+                let seat_id = row * self.geometry.columns + column;
+                out.push(match self.classify(seat_id) {
+                    None => '#',
+                    Some(Missing::Interior) => 'O',
+                    Some(_) => '.',
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Solution for Seats {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Seats::parse_with(input, Geometry::PUZZLE)
+    }
+
+    fn part_1(&self) -> Answer {
+        self.seat_ids.iter().max().copied().into()
+    }
+
+    fn part_2(&self) -> Answer {
+        self.seat_ids
+            .windows(2)
+            .find(|pair| pair[1] - pair[0] == 2)
+            .map(|pair| pair[0] + 1)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_decode_seat() {
+        let input = "FBFBBFFRLR";
+        let pass: BoardingPass = input.parse().unwrap();
+
+        assert_eq!((pass.row(), pass.column()), (44, 5));
+        assert_eq!(pass.seat_id(), 357);
+        assert_eq!(pass.encode(), input);
+    }
+
+    #[test]
+    fn test_geometry() {
+        let geometry = Geometry::new(16, 4).unwrap();
+        let pass = BoardingPass::decode("BFFBRL", geometry).unwrap();
+        assert_eq!((pass.row(), pass.column(), pass.seat_id()), (9, 2, 38));
+        assert_eq!(
+            BoardingPass::new(geometry, 9, 2).unwrap().to_string(),
+            "BFFBRL"
+        );
+        assert_eq!(
+            BoardingPass::new(geometry, 16, 0).unwrap_err().to_string(),
+            "seat in row 16 and column 0 is outside of a plane with 16 rows and 4 columns"
+        );
+        assert_eq!(
+            Geometry::new(12, 8),
+            Err(SeatError::NotPowerOfTwo("rows", 12))
+        );
+        assert!(Geometry::new(1 << 20, 1 << 13).is_err());
+        assert_eq!(Geometry::new(1, 1).unwrap().code_len(), 0);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let err = "FBFBBFFRL".parse::<BoardingPass>().unwrap_err();
+        assert_eq!(err.expected, "a seat code of 10 characters");
+        let err = "FBFBBFFRLX".parse::<BoardingPass>().unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (10, "'L' or 'R'"));
+        let err = "FBFBBFRRLR".parse::<BoardingPass>().unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (7, "R"));
+    }
+
+    #[test]
+    fn test_missing_seats() {
+        let geometry = Geometry::new(4, 2).unwrap();
+        // seats 2, 3, 5 and 6 are occupied
+        let seats = Seats::parse_with("FBL\nFBR\nBFR\nBBL", geometry).unwrap();
+        let missing: Vec<_> = seats
+            .missing()
+            .iter()
+            .map(|seat| (seat.pass.seat_id(), seat.missing))
+            .collect();
+        assert_eq!(
+            missing,
+            [
+                (0, Missing::Front),
+                (1, Missing::Front),
+                (4, Missing::Interior),
+                (7, Missing::Back),
+            ]
+        );
+        assert_eq!(seats.gaps(), [4..=4]);
+        assert_eq!(seats.render(), "0 ..\n1 ##\n2 O#\n3 #.\n");
+        assert_eq!(seats.part_2(), Answer::Unsigned(4));
+
+        let seats = Seats::parse_with("FBL\nFBR", geometry).unwrap();
+        assert_eq!(seats.part_2(), Answer::Unsolved);
+        assert!(seats.gaps().is_empty());
+        let seats = Seats::parse_with("", geometry).unwrap();
+        assert_eq!(seats.part_2(), Answer::Unsolved);
+        assert_eq!(seats.missing().len(), 8);
+    }
+}
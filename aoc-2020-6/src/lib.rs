@@ -0,0 +1,341 @@
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+use std::iter::FromIterator;
+
+use aoc_common::{Answer, ParseError, Solution};
+
+/// The questions `a` to `z` a person answered with yes, as a bitset with bit 0 for `a`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Answers(u32);
+
+impl Answers {
+    pub const ALL: Answers = Answers((1 << 26) - 1);
+
+    fn bit(question: char) -> Option<u32> {
+        if question.is_ascii_lowercase() {
+            Some(1 << (question as u32 - 'a' as u32))
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(self, question: char) -> bool {
+        Answers::bit(question).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn insert(&mut self, question: char) {
+        if let Some(bit) = Answers::bit(question) {
+            self.0 |= bit;
+        }
+    }
+
+    pub fn union(self, other: Answers) -> Answers {
+        Answers(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Answers) -> Answers {
+        Answers(self.0 & other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The questions in alphabetical order.
+    pub fn questions(self) -> impl Iterator<Item = char> {
+        ('a'..='z').filter(move |&question| self.contains(question))
+    }
+}
+
+impl FromIterator<char> for Answers {
+    /// Collects the questions, characters other than `a` to `z` are ignored.
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Answers {
+        let mut answers = Answers::default();
+        for question in iter {
+            answers.insert(question);
+        }
+        answers
+    }
+}
+
+/// The answers of a group with one entry per person.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Group {
+    people: Vec<Answers>,
+}
+
+impl Group {
+    pub fn new(people: Vec<Answers>) -> Group {
+        Group { people }
+    }
+
+    pub fn people(&self) -> &[Answers] {
+        &self.people
+    }
+
+    pub fn len(&self) -> usize {
+        self.people.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.people.is_empty()
+    }
+
+    /// How many people answered the question with yes.
+    pub fn count(&self, question: char) -> usize {
+        self.people
+            .iter()
+            .filter(|answers| answers.contains(question))
+            .count()
+    }
+
+    /// The questions answered by anyone (part 1).
+    pub fn anyone(&self) -> Answers {
+        self.people
+            .iter()
+            .fold(Answers::default(), |acc, &answers| acc.union(answers))
+    }
+
+    /// The questions answered by everyone (part 2), none for an empty group.
+    pub fn everyone(&self) -> Answers {
+        if self.people.is_empty() {
+            return Answers::default();
+        }
+        self.people
+            .iter()
+            .fold(Answers::ALL, |acc, &answers| acc.intersection(answers))
+    }
+
+    /// The questions answered by at least `k` people, e.g. `at_least(group.len().div_ceil(2))`
+    /// for at least half of the group.
+    pub fn at_least(&self, k: usize) -> Answers {
+        ('a'..='z')
+            .filter(|&question| self.count(question) >= k)
+            .collect()
+    }
+
+    /// The questions answered by more than half of the group.
+    pub fn majority(&self) -> Answers {
+        self.at_least(self.people.len() / 2 + 1)
+    }
+}
+
+/// An invalid line in the customs forms and the 1-based number of its group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormError {
+    pub group: usize,
+    pub error: ParseError,
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "group {}, {}", self.group, self.error)
+    }
+}
+
+impl Error for FormError {}
+
+/// How one question was answered over all groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuestionStats {
+    pub question: char,
+    /// Groups in which anyone answered the question
+    pub groups_anyone: usize,
+    /// Groups in which everyone answered the question
+    pub groups_everyone: usize,
+    /// People who answered the question
+    pub people: usize,
+}
+
+pub struct CustomsForms {
+    groups: Vec<Group>,
+}
+
+impl CustomsForms {
+    /// Parses the forms with one line of answers `a` to `z` per person and blank lines between
+    /// groups. Lines may end with `\r\n`, any number of blank lines separate groups.
+    pub fn parse_validated(input: &str) -> Result<CustomsForms, FormError> {
+        let mut groups = Vec::new();
+        let mut people = Vec::new();
+        for line in input.lines() {
+            if line.trim().is_empty() {
+                if !people.is_empty() {
+                    groups.push(Group::new(people));
+                    people = Vec::new();
+                }
+                continue;
+            }
+            if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+                return Err(FormError {
+                    group: groups.len() + 1,
+                    error: ParseError::new(
+                        input,
+                        &line[i..i + c.len_utf8()],
+                        "a question from 'a' to 'z'",
+                    ),
+                });
+            }
+            people.push(line.chars().collect());
+        }
+        if !people.is_empty() {
+            groups.push(Group::new(people));
+        }
+        Ok(CustomsForms { groups })
+    }
+
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    /// The number of people over all groups.
+    pub fn people(&self) -> usize {
+        self.groups.iter().map(Group::len).sum()
+    }
+
+    /// Statistics for every question from `a` to `z`.
+    pub fn question_stats(&self) -> Vec<QuestionStats> {
+        ('a'..='z')
+            .map(|question| QuestionStats {
+                question,
+                groups_anyone: self
+                    .groups
+                    .iter()
+                    .filter(|group| group.anyone().contains(question))
+                    .count(),
+                groups_everyone: self
+                    .groups
+                    .iter()
+                    .filter(|group| group.everyone().contains(question))
+                    .count(),
+                people: self.groups.iter().map(|group| group.count(question)).sum(),
+            })
+            .collect()
+    }
+
+    // The `n` first groups by `key` with their 1-based group numbers, ties keep the input order
+    fn top_groups<K: Ord>(&self, n: usize, key: impl Fn(&Group) -> K) -> Vec<(usize, &Group)> {
+        let mut groups: Vec<_> = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(i, group)| (i + 1, group))
+            .collect();
+        groups.sort_by_key(|(_, group)| Reverse(key(group)));
+        groups.truncate(n);
+        groups
+    }
+
+    /// The `n` groups with the most people and their 1-based group numbers.
+    pub fn largest_groups(&self, n: usize) -> Vec<(usize, &Group)> {
+        self.top_groups(n, Group::len)
+    }
+
+    /// The `n` groups with the most questions answered by everyone, larger groups first on a
+    /// tie, and their 1-based group numbers.
+    pub fn most_unanimous_groups(&self, n: usize) -> Vec<(usize, &Group)> {
+        self.top_groups(n, |group| (group.everyone().len(), group.len()))
+    }
+}
+
+impl Solution for CustomsForms {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        CustomsForms::parse_validated(input).map_err(|err| err.error)
+    }
+
+    fn part_1(&self) -> Answer {
+        let part1: usize = self.groups.iter().map(|group| group.anyone().len()).sum();
+        part1.into()
+    }
+
+    fn part_2(&self) -> Answer {
+        let part2: usize = self.groups.iter().map(|group| group.everyone().len()).sum();
+        part2.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+
+    #[test]
+    fn test_parts() {
+        let forms = CustomsForms::parse(TEST_INPUT).unwrap();
+        assert_eq!(forms.part_1(), Answer::Unsigned(11));
+        assert_eq!(forms.part_2(), Answer::Unsigned(6));
+    }
+
+    #[test]
+    fn test_group() {
+        let group = Group::new(vec![
+            "abc".chars().collect(),
+            "ab".chars().collect(),
+            "bz".chars().collect(),
+            "b".chars().collect(),
+        ]);
+        let questions = |answers: Answers| answers.questions().collect::<String>();
+        assert_eq!(questions(group.anyone()), "abcz");
+        assert_eq!(questions(group.everyone()), "b");
+        assert_eq!(questions(group.at_least(2)), "ab");
+        assert_eq!(questions(group.majority()), "b");
+        assert_eq!(group.at_least(0), Answers::ALL);
+        assert_eq!(group.count('a'), 2);
+
+        let empty = Group::default();
+        assert!(empty.anyone().is_empty());
+        assert!(empty.everyone().is_empty());
+    }
+
+    #[test]
+    fn test_statistics() {
+        let forms = CustomsForms::parse(TEST_INPUT).unwrap();
+        assert_eq!(forms.people(), 11);
+        let stats = forms.question_stats();
+        assert_eq!(stats.len(), 26);
+        assert_eq!(
+            stats[0],
+            QuestionStats {
+                question: 'a',
+                groups_anyone: 4,
+                groups_everyone: 3,
+                people: 8,
+            }
+        );
+        assert_eq!((stats[2].groups_anyone, stats[2].groups_everyone), (3, 1));
+        assert_eq!(stats[25].people, 0);
+
+        let numbers = |groups: Vec<(usize, &Group)>| -> Vec<usize> {
+            groups.into_iter().map(|(number, _)| number).collect()
+        };
+        assert_eq!(numbers(forms.largest_groups(2)), [4, 2]);
+        assert_eq!(numbers(forms.most_unanimous_groups(2)), [1, 4]);
+    }
+
+    #[test]
+    fn test_parse_validated() {
+        let input = "abc\r\n\r\na\r\nb\r\n\r\n\r\n\r\nab\n\n\n";
+        let forms = CustomsForms::parse_validated(input).unwrap();
+        assert_eq!(forms.groups().len(), 3);
+        assert_eq!(forms.part_1(), Answer::Unsigned(7));
+        assert_eq!(forms.part_2(), Answer::Unsigned(5));
+
+        let err = CustomsForms::parse_validated("abc\n\na\nb c\n")
+            .err()
+            .unwrap();
+        assert_eq!(err.group, 2);
+        assert_eq!((err.error.line, err.error.column), (4, 2));
+        assert_eq!(
+            err.to_string(),
+            "group 2, line 4, column 2: expected a question from 'a' to 'z', found \" \""
+        );
+        let err = CustomsForms::parse("ab\nAb").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert!(CustomsForms::parse("\n\n").unwrap().groups().is_empty());
+    }
+}
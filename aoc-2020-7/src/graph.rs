@@ -0,0 +1,211 @@
+use std::collections::{HashMap, VecDeque};
+
+/// The interned ID of a bag color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BagId(usize);
+
+/// A rule that `container` bags contain `count` `content` bags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub container: BagId,
+    pub content: BagId,
+    pub count: usize,
+}
+
+/// The bag rules as a graph with an edge from every container to each of its contents.
+///
+/// Every edge is stored once, the forward and reverse adjacency lists refer to it by index.
+#[derive(Debug, Clone, Default)]
+pub struct BagGraph {
+    ids: HashMap<String, BagId>,
+    colors: Vec<String>,
+    edges: Vec<Edge>,
+    forward: Vec<Vec<usize>>,
+    reverse: Vec<Vec<usize>>,
+}
+
+impl BagGraph {
+    pub fn new() -> BagGraph {
+        BagGraph::default()
+    }
+
+    /// The ID of the color, a new one if the color wasn't seen before.
+    pub fn intern(&mut self, color: &str) -> BagId {
+        if let Some(&id) = self.ids.get(color) {
+            return id;
+        }
+        let id = BagId(self.colors.len());
+        self.ids.insert(color.into(), id);
+        self.colors.push(color.into());
+        self.forward.push(Vec::new());
+        self.reverse.push(Vec::new());
+        id
+    }
+
+    pub fn add_edge(&mut self, container: BagId, content: BagId, count: usize) {
+        let index = self.edges.len();
+        self.edges.push(Edge {
+            container,
+            content,
+            count,
+        });
+        self.forward[container.0].push(index);
+        self.reverse[content.0].push(index);
+    }
+
+    pub fn id(&self, color: &str) -> Option<BagId> {
+        self.ids.get(color).copied()
+    }
+
+    pub fn color(&self, id: BagId) -> &str {
+        &self.colors[id.0]
+    }
+
+    /// The number of colors.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// The direct contents of a bag.
+    pub fn edges_from(&self, id: BagId) -> impl Iterator<Item = &Edge> {
+        self.forward[id.0]
+            .iter()
+            .map(move |&index| &self.edges[index])
+    }
+
+    /// The bags directly containing a bag.
+    pub fn edges_to(&self, id: BagId) -> impl Iterator<Item = &Edge> {
+        self.reverse[id.0]
+            .iter()
+            .map(move |&index| &self.edges[index])
+    }
+
+    /// Every color which eventually contains a bag of `color` (part 1), sorted by name.
+    pub fn containers_of(&self, color: &str) -> Option<Vec<&str>> {
+        let start = self.id(color)?;
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from(vec![start]);
+        let mut containers = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            for edge in self.edges_to(id) {
+                if !seen[edge.container.0] {
+                    seen[edge.container.0] = true;
+                    containers.push(self.color(edge.container));
+                    queue.push_back(edge.container);
+                }
+            }
+        }
+        containers.sort_unstable();
+        Some(containers)
+    }
+
+    /// Bags which eventually contain themselves, found with a depth first search without
+    /// recursion. There is one path for every edge back into the current search path, so at least
+    /// one if the rules have a cycle. Every path starts and ends with the same color, its last
+    /// edge closes the cycle.
+    pub fn cycles(&self) -> Vec<Vec<BagId>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            New,
+            Active,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.len() {
+            if state[start] != State::New {
+                continue;
+            }
+            state[start] = State::Active;
+            // the current path with the index of the next edge to follow from each color
+            let mut stack = vec![(BagId(start), 0)];
+            while let Some((id, next)) = stack.last_mut() {
+                let id = *id;
+                let index = match self.forward[id.0].get(*next) {
+                    Some(&index) => index,
+                    None => {
+                        state[id.0] = State::Done;
+                        stack.pop();
+                        continue;
+                    }
+                };
+                *next += 1;
+                let content = self.edges[index].content;
+                match state[content.0] {
+                    State::New => {
+                        state[content.0] = State::Active;
+                        stack.push((content, 0));
+                    }
+                    State::Active => {
+                        let first = stack.iter().position(|&(id, _)| id == content).unwrap();
+                        let mut cycle: Vec<_> = stack[first..].iter().map(|&(id, _)| id).collect();
+                        cycle.push(content);
+                        cycles.push(cycle);
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        cycles
+    }
+
+    // How many bags of each color a bag of `root` holds, counting `root` itself once. Colors are
+    // visited in topological order so every bag is complete before its contents are expanded, bags
+    // in a cycle are never complete and not counted.
+    fn multiplicities(&self, root: BagId) -> Vec<usize> {
+        // incoming edges within the part of the graph reachable from root
+        let mut reachable = vec![false; self.len()];
+        reachable[root.0] = true;
+        let mut stack = vec![root];
+        let mut incoming = vec![0; self.len()];
+        while let Some(id) = stack.pop() {
+            for edge in self.edges_from(id) {
+                incoming[edge.content.0] += 1;
+                if !reachable[edge.content.0] {
+                    reachable[edge.content.0] = true;
+                    stack.push(edge.content);
+                }
+            }
+        }
+
+        let mut counts = vec![0; self.len()];
+        counts[root.0] = 1;
+        let mut ready = vec![root];
+        while let Some(id) = ready.pop() {
+            // root is already expanded, going back to it can only close a cycle
+            for edge in self.edges_from(id).filter(|edge| edge.content != root) {
+                counts[edge.content.0] += counts[id.0] * edge.count;
+                incoming[edge.content.0] -= 1;
+                if incoming[edge.content.0] == 0 {
+                    ready.push(edge.content);
+                }
+            }
+        }
+        counts
+    }
+
+    /// Every color inside a bag of `color` with the total number of such bags, sorted by name.
+    pub fn contents_of(&self, color: &str) -> Option<Vec<(&str, usize)>> {
+        let root = self.id(color)?;
+        let mut contents: Vec<_> = self
+            .multiplicities(root)
+            .into_iter()
+            .enumerate()
+            .filter(|&(id, count)| id != root.0 && count > 0)
+            .map(|(id, count)| (self.color(BagId(id)), count))
+            .collect();
+        contents.sort_unstable();
+        Some(contents)
+    }
+
+    /// The number of bags inside a bag of `color` (part 2).
+    pub fn total_bags_inside(&self, color: &str) -> Option<usize> {
+        let root = self.id(color)?;
+        Some(self.multiplicities(root).iter().sum::<usize>() - 1)
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{parse_token, Answer, ParseError, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+mod graph;
+
+pub use graph::{BagGraph, BagId, Edge};
+
+// One line of the rules, the colors are slices of the line
+struct Rule<'a> {
+    container: &'a str,
+    contents: Vec<(usize, &'a str)>,
+}
+
+fn parse_line(input: &str) -> Result<Rule<'_>, ParseError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^([a-z]+ [a-z]+) bags? contain (.*)$").unwrap();
+        static ref RE_CONTENT: Regex = Regex::new(r"([0-9]+) ([a-z]+ [a-z]+) bags?").unwrap();
+    }
+    let captures = RE
+        .captures(input)
+        .ok_or_else(|| ParseError::new(input, input, "\"<color> bags contain <contents>\""))?;
+    let container = captures.get(1).unwrap().as_str();
+    let content = captures.get(2).unwrap().as_str();
+    if content == "no other bags." {
+        return Ok(Rule {
+            container,
+            contents: Vec::new(),
+        });
+    }
+    let mut contents = Vec::new();
+    for v in content.split(',') {
+        let captures = RE_CONTENT
+            .captures(v)
+            .ok_or_else(|| ParseError::new(input, v.trim(), "\"<count> <color> bags\""))?;
+        let bag = captures.get(2).unwrap().as_str();
+        let count = parse_token(input, captures.get(1).unwrap().as_str(), "a count")?;
+        contents.push((count, bag));
+    }
+
+    Ok(Rule {
+        container,
+        contents,
+    })
+}
+
+/// A problem in the rules, the `ParseError` points at the offending color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    /// A line which isn't a rule
+    Syntax(ParseError),
+    /// A second rule for a color, `first_line` is the line of the first one
+    Duplicate {
+        error: ParseError,
+        first_line: usize,
+    },
+    /// Contents of a color without a rule
+    Undefined(ParseError),
+    /// Bags which contain themselves, the path starts and ends with the same color
+    Cycle {
+        error: ParseError,
+        path: Vec<String>,
+    },
+}
+
+impl RuleError {
+    pub fn error(&self) -> &ParseError {
+        match self {
+            RuleError::Syntax(error)
+            | RuleError::Duplicate { error, .. }
+            | RuleError::Undefined(error)
+            | RuleError::Cycle { error, .. } => error,
+        }
+    }
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error())
+    }
+}
+
+impl Error for RuleError {}
+
+pub struct Rules {
+    graph: BagGraph,
+}
+
+impl Rules {
+    /// Parses the rules and checks that every color has exactly one rule and no bag eventually
+    /// contains itself. All problems are reported, ordered by their position.
+    pub fn parse_validated(input: &str) -> Result<Rules, Vec<RuleError>> {
+        let rules = input
+            .lines()
+            .map(|line| parse_line(line).map_err(|err| err.within(input, line)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| vec![RuleError::Syntax(err)])?;
+
+        let mut graph = BagGraph::new();
+        let mut errors = Vec::new();
+        // the line of the rule of each color
+        let mut defined = HashMap::new();
+        // where each edge is written, to locate cycles
+        let mut references = HashMap::new();
+        for (line, rule) in (1..).zip(&rules) {
+            let container = graph.intern(rule.container);
+            if let Some(&first_line) = defined.get(&container) {
+                errors.push(RuleError::Duplicate {
+                    error: ParseError::new(
+                        input,
+                        rule.container,
+                        format!(
+                            "one rule per color, {} bags are defined on line {}",
+                            rule.container, first_line
+                        ),
+                    ),
+                    first_line,
+                });
+                continue;
+            }
+            defined.insert(container, line);
+            for &(count, content) in &rule.contents {
+                let id = graph.intern(content);
+                graph.add_edge(container, id, count);
+                references.entry((container, id)).or_insert(content);
+            }
+        }
+
+        for rule in &rules {
+            for &(_, content) in &rule.contents {
+                // contents of duplicate rules aren't in the graph
+                let has_rule = graph
+                    .id(content)
+                    .is_some_and(|id| defined.contains_key(&id));
+                if !has_rule {
+                    errors.push(RuleError::Undefined(ParseError::new(
+                        input,
+                        content,
+                        "a color with a rule",
+                    )));
+                }
+            }
+        }
+
+        for cycle in graph.cycles() {
+            let path: Vec<String> = cycle.iter().map(|&id| graph.color(id).into()).collect();
+            let closing = references[&(cycle[cycle.len() - 2], cycle[cycle.len() - 1])];
+            errors.push(RuleError::Cycle {
+                error: ParseError::new(
+                    input,
+                    closing,
+                    format!(
+                        "bags which don't contain themselves ({})",
+                        path.join(" -> ")
+                    ),
+                ),
+                path,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(Rules { graph })
+        } else {
+            errors.sort_by_key(|err| (err.error().line, err.error().column));
+            Err(errors)
+        }
+    }
+
+    pub fn graph(&self) -> &BagGraph {
+        &self.graph
+    }
+}
+
+impl Solution for Rules {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Rules::parse_validated(input).map_err(|errors| errors[0].error().clone())
+    }
+
+    fn part_1(&self) -> Answer {
+        self.graph
+            .containers_of("shiny gold")
+            .map(|containers| containers.len())
+            .into()
+    }
+
+    fn part_2(&self) -> Answer {
+        self.graph.total_bags_inside("shiny gold").into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    #[test]
+    fn test_parts() {
+        let rules = Rules::parse(TEST_INPUT).unwrap();
+        assert_eq!(rules.part_1(), Answer::Unsigned(4));
+        assert_eq!(rules.part_2(), Answer::Unsigned(32));
+    }
+
+    #[test]
+    fn test_graph() {
+        let rules = Rules::parse(TEST_INPUT).unwrap();
+        let graph = rules.graph();
+        assert_eq!(graph.len(), 9);
+        assert_eq!(
+            graph.containers_of("muted yellow").unwrap(),
+            ["dark orange", "light red"]
+        );
+        assert_eq!(
+            graph.contents_of("shiny gold").unwrap(),
+            [
+                ("dark olive", 1),
+                ("dotted black", 16),
+                ("faded blue", 13),
+                ("vibrant plum", 2)
+            ]
+        );
+        // 2 shiny gold bags with 32 bags each, 9 faded blue bags
+        assert_eq!(graph.total_bags_inside("muted yellow"), Some(2 + 64 + 9));
+        assert_eq!(graph.total_bags_inside("faded blue"), Some(0));
+        assert_eq!(graph.containers_of("light red").unwrap().len(), 0);
+        assert_eq!(graph.contents_of("mauve"), None);
+
+        let id = graph.id("bright white").unwrap();
+        assert_eq!(graph.color(id), "bright white");
+        let edges: Vec<_> = graph
+            .edges_from(id)
+            .map(|edge| (graph.color(edge.content), edge.count))
+            .collect();
+        assert_eq!(edges, [("shiny gold", 1)]);
+        assert_eq!(graph.edges_to(id).count(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_and_undefined() {
+        let input = "light red bags contain 1 bright white bag.
+bright white bags contain 2 faded blue bags, 1 dotted black bag.
+faded blue bags contain no other bags.
+light red bags contain no other bags.";
+        let errors = Rules::parse_validated(input).err().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            RuleError::Undefined(ParseError {
+                line: 2,
+                column: 48,
+                text: "dotted black".into(),
+                expected: "a color with a rule".into(),
+            })
+        );
+        match &errors[1] {
+            RuleError::Duplicate { error, first_line } => {
+                assert_eq!((error.line, error.column, *first_line), (4, 1, 1));
+                assert_eq!(
+                    error.to_string(),
+                    "line 4, column 1: expected one rule per color, light red bags are \
+                     defined on line 1, found \"light red\""
+                );
+            }
+            err => panic!("expected a duplicate, found {:?}", err),
+        }
+
+        let err = Rules::parse(input).err().unwrap();
+        assert_eq!(err.text, "dotted black");
+    }
+
+    #[test]
+    fn test_undefined_in_duplicate() {
+        let input = "light red bags contain no other bags.
+light red bags contain 1 dark olive bag.";
+        let errors = Rules::parse_validated(input).err().unwrap();
+        let positions: Vec<_> = errors
+            .iter()
+            .map(|err| {
+                (
+                    err.error().line,
+                    err.error().column,
+                    err.error().text.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(positions, [(2, 1, "light red"), (2, 26, "dark olive")]);
+        assert!(matches!(errors[1], RuleError::Undefined(_)));
+    }
+
+    #[test]
+    fn test_cycles() {
+        let input = "light red bags contain 1 bright white bag, 2 shiny gold bags.
+bright white bags contain 1 shiny gold bag.
+shiny gold bags contain 3 light red bags.
+faded blue bags contain 1 faded blue bag.";
+        let errors = Rules::parse_validated(input).err().unwrap();
+        let cycles: Vec<_> = errors
+            .iter()
+            .map(|err| match err {
+                RuleError::Cycle { error, path } => (error.line, error.column, path.join(" -> ")),
+                err => panic!("expected a cycle, found {:?}", err),
+            })
+            .collect();
+        assert_eq!(
+            cycles,
+            [
+                (
+                    3,
+                    27,
+                    "light red -> bright white -> shiny gold -> light red".into()
+                ),
+                (4, 27, "faded blue -> faded blue".into()),
+            ]
+        );
+
+        // the graph queries terminate on cycles as well
+        let mut graph = BagGraph::new();
+        let (red, white) = (graph.intern("light red"), graph.intern("bright white"));
+        graph.add_edge(red, white, 2);
+        graph.add_edge(white, red, 3);
+        assert_eq!(
+            graph.containers_of("light red").unwrap(),
+            ["bright white", "light red"]
+        );
+        assert_eq!(graph.total_bags_inside("light red"), Some(2));
+    }
+
+    #[test]
+    fn test_long_cycle() {
+        // colors like "aa ab" are all distinct
+        let color = |i: usize| {
+            let letter = |i: usize| (b'a' + (i % 26) as u8) as char;
+            format!(
+                "{}{} {}{}",
+                letter(i / 17576),
+                letter(i / 676),
+                letter(i / 26),
+                letter(i)
+            )
+        };
+        let n = 5_000;
+        let mut input = String::new();
+        for i in 0..n {
+            input += &format!("{} bags contain 1 {} bag.\n", color(i), color((i + 1) % n));
+        }
+        let errors = Rules::parse_validated(&input).err().unwrap();
+        match &errors[..] {
+            [RuleError::Cycle { path, .. }] => assert_eq!(path.len(), n + 1),
+            _ => panic!("expected one cycle"),
+        }
+    }
+}
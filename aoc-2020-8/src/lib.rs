@@ -0,0 +1,187 @@
+use std::str::FromStr;
+
+use aoc_common::{parse_lines, parse_token, Answer, ParseError, Solution};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Instruction {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let op = parts
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "an operation"))?;
+        let arg: i32 = parse_token(
+            s,
+            parts
+                .next()
+                .ok_or_else(|| ParseError::missing(s, "an argument"))?,
+            "a signed number",
+        )?;
+        match op {
+            "nop" => Ok(Instruction::Nop(arg)),
+            "acc" => Ok(Instruction::Acc(arg)),
+            "jmp" => Ok(Instruction::Jmp(arg)),
+            _ => Err(ParseError::new(s, op, "one of nop, acc or jmp")),
+        }
+    }
+}
+
+// The target of a jump in a program of `len` instructions, it may end right after the program
+fn jump_target(pc: usize, offset: i32, len: usize) -> Option<usize> {
+    pc.checked_add_signed(offset as isize)
+        .filter(|&target| target <= len)
+}
+
+struct Machine {
+    accumulator: i32,
+    pc: usize,
+    code: Vec<Instruction>,
+}
+
+impl Machine {
+    pub fn new(code: Vec<Instruction>) -> Machine {
+        Machine {
+            accumulator: 0,
+            pc: 0,
+            code,
+        }
+    }
+
+    // Executes the instruction at pc, None if it jumps outside of the program. A jump right
+    // after the last instruction ends the program.
+    pub fn step(&mut self) -> Option<usize> {
+        let pc = match self.code[self.pc] {
+            Instruction::Nop(_) => self.pc + 1,
+            Instruction::Acc(acc) => {
+                self.accumulator += acc;
+                self.pc + 1
+            }
+            Instruction::Jmp(offset) => jump_target(self.pc, offset, self.code.len())?,
+        };
+        self.pc = pc;
+        Some(pc)
+    }
+
+    pub fn try_run_to_end(&mut self) -> Option<i32> {
+        let mut visited = vec![0usize];
+        loop {
+            self.step()?;
+            if self.pc >= self.code.len() {
+                return Some(self.accumulator);
+            }
+            if visited.contains(&self.pc) {
+                return None;
+            }
+            visited.push(self.pc);
+        }
+    }
+
+    pub fn find_patched_result(&self) -> Option<i32> {
+        for offset in 0..self.code.len() {
+            let patch = match self.code[offset] {
+                Instruction::Nop(arg) => Instruction::Jmp(arg),
+                Instruction::Jmp(arg) => Instruction::Nop(arg),
+                _ => continue,
+            };
+            let mut machine = Machine::new(self.code.clone());
+            machine.code[offset] = patch;
+
+            if let Some(acc) = machine.try_run_to_end() {
+                return Some(acc);
+            }
+        }
+        None
+    }
+}
+
+pub struct Program {
+    code: Vec<Instruction>,
+}
+
+impl Solution for Program {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let code = parse_lines(input, Instruction::from_str)?;
+        if code.is_empty() {
+            return Err(ParseError::missing(input, "an instruction"));
+        }
+        for (pc, (line, instruction)) in input.lines().zip(&code).enumerate() {
+            if let Instruction::Jmp(offset) = instruction {
+                if jump_target(pc, *offset, code.len()).is_none() {
+                    let argument = line.split_whitespace().nth(1).unwrap();
+                    return Err(ParseError::new(
+                        input,
+                        argument,
+                        format!(
+                            "a jump to a line from 1 to {} or right after the last one",
+                            code.len()
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(Program { code })
+    }
+
+    fn part_1(&self) -> Answer {
+        let mut machine = Machine::new(self.code.clone());
+
+        let mut visited = vec![0usize];
+        loop {
+            match machine.step() {
+                Some(pc) if visited.contains(&pc) => return machine.accumulator.into(),
+                Some(pc) if pc < self.code.len() => visited.push(pc),
+                // the program ends without repeating an instruction
+                _ => return Answer::Unsolved,
+            }
+        }
+    }
+
+    fn part_2(&self) -> Answer {
+        Machine::new(self.code.clone()).find_patched_result().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    #[test]
+    fn test_parts() {
+        let program = Program::parse(TEST_INPUT).unwrap();
+        assert_eq!(program.part_1(), Answer::Signed(5));
+        assert_eq!(program.part_2(), Answer::Signed(8));
+    }
+
+    #[test]
+    fn test_invalid_programs() {
+        let err = Program::parse("").err().unwrap();
+        assert_eq!(err.expected, "an instruction");
+        let err = Program::parse("nop +0\njmp -5").err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "-5"));
+        assert!(Program::parse("jmp +2\njmp +3").is_err());
+
+        // a jump right after the last instruction ends the program
+        let program = Program::parse("acc +1\njmp +1").unwrap();
+        assert_eq!(program.part_1(), Answer::Unsolved);
+        // patching the nop would jump outside of the program
+        let program = Program::parse("nop +5\njmp +0\njmp -1").unwrap();
+        assert_eq!(program.part_2(), Answer::Unsolved);
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use aoc_common::{parse_lines, parse_token, Answer, ParseError, Solution};
+
+fn check_if_valid(preamble: &VecDeque<i64>, number: i64) -> bool {
+    for (i, a) in preamble.iter().enumerate() {
+        for b in preamble.iter().skip(i + 1) {
+            if *a + *b == number {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+const PREAMBLE_LEN: usize = 25;
+
+// None if every number is valid or there are no numbers after the preamble
+fn find_invalid_number(numbers: &[i64], preamble_len: usize) -> Option<i64> {
+    let mut preamble = VecDeque::<i64>::new();
+    preamble.extend(numbers.get(..preamble_len)?);
+
+    for number in numbers.iter().skip(preamble_len) {
+        if !check_if_valid(&preamble, *number) {
+            return Some(*number);
+        }
+        preamble.pop_front();
+        preamble.push_back(*number);
+    }
+    None
+}
+
+fn find_encryption_weakness(numbers: &[i64], invalid_number: i64) -> Option<i64> {
+    for i in 0..numbers.len() {
+        let mut sum = 0;
+        let mut j = i;
+        while sum < invalid_number && j < numbers.len() {
+            sum += numbers[j];
+            // the range needs at least two numbers
+            if sum == invalid_number && j > i {
+                let min = numbers[i..=j].iter().min().unwrap();
+                let max = numbers[i..=j].iter().max().unwrap();
+                return Some(min + max);
+            }
+            j += 1;
+        }
+    }
+    None
+}
+
+pub struct XmasData {
+    numbers: Vec<i64>,
+}
+
+impl Solution for XmasData {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let numbers = parse_lines(input, |line| parse_token(line, line, "a number"))?;
+        Ok(XmasData { numbers })
+    }
+
+    fn part_1(&self) -> Answer {
+        find_invalid_number(&self.numbers, PREAMBLE_LEN).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        find_invalid_number(&self.numbers, PREAMBLE_LEN)
+            .and_then(|invalid_number| find_encryption_weakness(&self.numbers, invalid_number))
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: [i64; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn test_example() {
+        assert_eq!(find_invalid_number(&TEST_INPUT, 5), Some(127));
+        assert_eq!(find_encryption_weakness(&TEST_INPUT, 127), Some(62));
+    }
+
+    #[test]
+    fn test_short_input() {
+        let data = XmasData::parse("1\n2\n3").unwrap();
+        assert_eq!(data.part_1(), Answer::Unsolved);
+        assert_eq!(data.part_2(), Answer::Unsolved);
+        // the sum of all numbers is too small, the search ends at the last one
+        assert_eq!(find_encryption_weakness(&[1, 2, 3], 100), None);
+    }
+}
@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error in the puzzle input.
+///
+/// Lines and columns are 1-based and count bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+// Byte offset of `part` inside of `input`, or the end of `input` if `part` isn't a slice of it
+fn offset_of(input: &str, part: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize).wrapping_sub(start);
+    if offset <= input.len() {
+        offset
+    } else {
+        input.len()
+    }
+}
+
+// 1-based line and column of a byte offset
+fn position_of(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, offset - line_start + 1)
+}
+
+impl ParseError {
+    /// Creates an error for `text`, which has to be a slice of `input`.
+    pub fn new(input: &str, text: &str, expected: impl Into<String>) -> ParseError {
+        let (line, column) = position_of(input, offset_of(input, text));
+        ParseError {
+            line,
+            column,
+            text: text.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error for input which ended before `expected` was found.
+    pub fn missing(input: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(input, &input[input.len()..], expected)
+    }
+
+    /// Moves an error reported relative to `part` so it is relative to `input`.
+    ///
+    /// `part` has to be a slice of `input`, e.g. a line or a record.
+    pub fn within(mut self, input: &str, part: &str) -> ParseError {
+        let (line, column) = position_of(input, offset_of(input, part));
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, "found end of input")
+        } else {
+            write!(f, "found {:?}", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `text`, a slice of `input`, reporting `expected` if that fails.
+pub fn parse_token<T: FromStr>(input: &str, text: &str, expected: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(input, text, expected))
+}
+
+/// Parses every line of `input`, locating errors in the whole input.
+pub fn parse_lines<T, F>(input: &str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|err| err.within(input, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_position() {
+        let input = "1\n2\nthree\n4";
+        let err =
+            parse_lines(input, |line| parse_token::<u32>(line, line, "a number")).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 1);
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: expected a number, found \"three\""
+        );
+    }
+
+    #[test]
+    fn test_error_within_line() {
+        let input = "a b\nc d";
+        let line = input.lines().nth(1).unwrap();
+        let err = ParseError::new(line, &line[2..], "e").within(input, line);
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_missing() {
+        let err = ParseError::missing("1-3", "a letter");
+        assert_eq!(err.column, 4);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected a letter, found end of input"
+        );
+    }
+}
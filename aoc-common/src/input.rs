@@ -0,0 +1,108 @@
+use std::convert::Infallible;
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, prelude::*, BufReader};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Environment variable pointing to a directory with one `dayNN.txt` per day.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl FromStr for Input {
+    type Err = Infallible;
+
+    /// `-` means stdin, everything else is a path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            Ok(Input::Stdin)
+        } else {
+            Ok(Input::File(s.into()))
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "<stdin>"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Input {
+    /// The input of `day` in an inputs directory, e.g. `inputs/day08.txt`.
+    pub fn in_dir(dir: &Path, day: u32) -> Input {
+        Input::File(dir.join(format!("day{:02}.txt", day)))
+    }
+
+    /// Picks the explicit input if there is one, then the inputs directory and finally `default`.
+    pub fn resolve(
+        explicit: Option<Input>,
+        inputs_dir: Option<&Path>,
+        day: u32,
+        default: impl Into<PathBuf>,
+    ) -> Input {
+        match (explicit, inputs_dir) {
+            (Some(input), _) => input,
+            (None, Some(dir)) => Input::in_dir(dir, day),
+            (None, None) => Input::File(default.into()),
+        }
+    }
+
+    /// Opens the input for reading it line by line without loading it into memory.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Input::Stdin => Ok(Box::new(io::stdin().lock())),
+            Input::File(path) => File::open(path)
+                .map(|f| Box::new(BufReader::new(f)) as Box<dyn BufRead>)
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", self, err))),
+        }
+    }
+
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut contents = String::new();
+        let result = match self {
+            Input::Stdin => io::stdin().read_to_string(&mut contents),
+            Input::File(path) => File::open(path).and_then(|mut f| f.read_to_string(&mut contents)),
+        };
+        result
+            .map(|_| contents)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", self, err)))
+    }
+}
+
+/// The inputs directory configured through `AOC_INPUTS_DIR`.
+pub fn inputs_dir() -> Option<PathBuf> {
+    env::var_os(INPUTS_DIR_VAR).map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let dir = Path::new("inputs");
+        assert_eq!(
+            Input::resolve(Some("-".parse().unwrap()), Some(dir), 8, "input.txt"),
+            Input::Stdin
+        );
+        assert_eq!(
+            Input::resolve(None, Some(dir), 8, "input.txt"),
+            Input::File("inputs/day08.txt".into())
+        );
+        assert_eq!(
+            Input::resolve(None, None, 8, "input.txt"),
+            Input::File("input.txt".into())
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::process;
+
+mod error;
+mod input;
+
+pub use error::{parse_lines, parse_token, ParseError};
+pub use input::{inputs_dir, Input, INPUTS_DIR_VAR};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident, $target:ty, $($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Signed, i64, i32, i64);
+impl_from!(Unsigned, u64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+/// A parsed puzzle input which can answer both parts of the puzzle.
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    fn part_1(&self) -> Answer;
+
+    fn part_2(&self) -> Answer;
+}
+
+/// Parses `input` into a type erased solution, so days can be put into one table.
+pub fn parse_boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, ParseError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+pub fn print_answers(solution: &dyn Solution) {
+    println!("Part 1: {}", solution.part_1());
+    println!("Part 2: {}", solution.part_2());
+}
+
+fn solve<S: Solution>(day: u32) -> Result<(), Box<dyn Error>> {
+    let explicit = env::args().nth(1).and_then(|arg| arg.parse().ok());
+    let input = Input::resolve(explicit, inputs_dir().as_deref(), day, "input.txt");
+    let contents = input.read_to_string()?;
+
+    let solution = S::parse(&contents).map_err(|err| format!("{}: {}", input, err))?;
+    print_answers(&solution);
+    Ok(())
+}
+
+/// Entry point of the per day binaries, prints a readable message instead of a debug dump on errors.
+///
+/// The input is taken from the first argument (`-` for stdin), `$AOC_INPUTS_DIR/dayNN.txt` or
+/// `input.txt` in the current directory.
+pub fn run<S: Solution>(day: u32) {
+    if let Err(err) = solve::<S>(day) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from_option() {
+        assert_eq!(Answer::from(Some(42u32)), Answer::Unsigned(42));
+        assert_eq!(Answer::from(None::<i32>), Answer::Unsolved);
+    }
+}
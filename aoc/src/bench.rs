@@ -0,0 +1,93 @@
+use std::error::Error;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::{parse_day, read_input};
+
+/// Timings of one step of a day over all iterations.
+#[derive(Debug, Serialize)]
+pub struct Timing {
+    pub day: u32,
+    pub step: &'static str,
+    pub iterations: usize,
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub max_ns: u128,
+}
+
+impl Timing {
+    fn new(day: u32, step: &'static str, mut samples: Vec<Duration>) -> Timing {
+        samples.sort_unstable();
+        Timing {
+            day,
+            step,
+            iterations: samples.len(),
+            min_ns: samples[0].as_nanos(),
+            median_ns: samples[samples.len() / 2].as_nanos(),
+            max_ns: samples[samples.len() - 1].as_nanos(),
+        }
+    }
+}
+
+/// Parts which don't finish on the real input, skipped unless their day is given explicitly.
+pub const SLOW_PARTS: &[(u32, u32)] = &[(13, 2)];
+
+fn time<T>(samples: &mut Vec<Duration>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = black_box(f());
+    samples.push(start.elapsed());
+    result
+}
+
+/// Times parsing and the selected parts of `day` over `iterations` runs.
+pub fn bench_day(
+    day: u32,
+    parts: &[u32],
+    iterations: usize,
+    inputs_dir: Option<&Path>,
+) -> Result<Vec<Timing>, Box<dyn Error>> {
+    let (input, contents) = read_input(day, None, inputs_dir)?;
+
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part_1 = Vec::with_capacity(iterations);
+    let mut part_2 = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let solution = time(&mut parse, || parse_day(day, &input, &contents))?;
+        if parts.contains(&1) {
+            time(&mut part_1, || solution.part_1());
+        }
+        if parts.contains(&2) {
+            time(&mut part_2, || solution.part_2());
+        }
+    }
+
+    let mut timings = vec![Timing::new(day, "parse", parse)];
+    if !part_1.is_empty() {
+        timings.push(Timing::new(day, "part 1", part_1));
+    }
+    if !part_2.is_empty() {
+        timings.push(Timing::new(day, "part 2", part_2));
+    }
+    Ok(timings)
+}
+
+pub fn print_table(timings: &[Timing]) {
+    println!(
+        "{:<4} {:<7} {:>12} {:>12} {:>12}",
+        "day", "step", "min", "median", "max"
+    );
+    for timing in timings {
+        let format = |ns: u128| format!("{:?}", Duration::from_nanos(ns as u64));
+        println!(
+            "{:<4} {:<7} {:>12} {:>12} {:>12}",
+            format!("{:02}", timing.day),
+            timing.step,
+            format(timing.min_ns),
+            format(timing.median_ns),
+            format(timing.max_ns)
+        );
+    }
+}
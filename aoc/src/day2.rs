@@ -0,0 +1,181 @@
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_2020_2::{parse_policy, CountRange, PasswordDatabase, Policies, ReportEntry};
+use aoc_common::{Input, Solution};
+use clap::{Args, Subcommand, ValueEnum};
+use serde_json::json;
+
+use crate::read_input;
+
+#[derive(Args)]
+pub struct PolicyArgs {
+    /// Policy to check, e.g. count-range, positional-xor, positional-and, "forbidden iol" or
+    /// "regex ^[a-z]{8,}$"
+    #[arg(long)]
+    policy: Vec<String>,
+    /// File with one policy per line, # starts a comment
+    #[arg(long)]
+    policy_file: Option<PathBuf>,
+    /// Input file or - for stdin
+    #[arg(long)]
+    input: Option<Input>,
+}
+
+impl PolicyArgs {
+    // All given policies, or the policy of part 1 if there are none
+    fn policies(&self) -> Result<Policies, Box<dyn Error>> {
+        let mut policies = Vec::new();
+        for policy in &self.policy {
+            policies.push(parse_policy(policy).map_err(|err| format!("--policy: {}", err))?);
+        }
+        if let Some(path) = &self.policy_file {
+            let config =
+                fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            let file =
+                Policies::parse(&config).map_err(|err| format!("{}: {}", path.display(), err))?;
+            policies.extend(file.0);
+        }
+        if policies.is_empty() {
+            policies.push(Box::new(CountRange));
+        }
+        Ok(Policies(policies))
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Day2Command {
+    /// Count the passwords which fulfill all given policies
+    Validate(PolicyArgs),
+    /// List the passwords violating a policy with the reasons
+    Report {
+        #[command(flatten)]
+        policy: PolicyArgs,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+        /// Include the lines fulfilling all policies
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+fn print_table(entries: &[ReportEntry]) {
+    println!("{:>5}  {:<30}  reasons", "line", "entry");
+    for entry in entries {
+        let rule = entry.rule;
+        let text = format!(
+            "{}-{} {}: {}",
+            rule.range.min, rule.range.max, rule.letter as char, rule.string
+        );
+        let reasons = if entry.violations.is_empty() {
+            "ok".to_string()
+        } else {
+            entry
+                .violations
+                .iter()
+                .map(|violation| format!("{}: {}", violation.policy, violation.reason))
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+        println!("{:>5}  {:<30}  {}", entry.line, text, reasons);
+    }
+}
+
+// One row per violation, valid lines get a single row with empty policy and reason
+fn print_csv(entries: &[ReportEntry]) -> Result<(), Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    writer.write_record([
+        "line", "min", "max", "letter", "password", "policy", "reason",
+    ])?;
+    for entry in entries {
+        let rule = entry.rule;
+        let fields = [
+            entry.line.to_string(),
+            rule.range.min.to_string(),
+            rule.range.max.to_string(),
+            (rule.letter as char).to_string(),
+            rule.string.clone(),
+        ];
+        if entry.violations.is_empty() {
+            writer.write_record(fields.iter().map(String::as_str).chain(["", ""]))?;
+        }
+        for violation in &entry.violations {
+            writer.write_record(
+                fields
+                    .iter()
+                    .map(String::as_str)
+                    .chain([violation.policy, violation.reason.as_str()]),
+            )?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn print_json(entries: &[ReportEntry]) -> Result<(), Box<dyn Error>> {
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "line": entry.line,
+                "min": entry.rule.range.min,
+                "max": entry.rule.range.max,
+                "letter": (entry.rule.letter as char).to_string(),
+                "password": entry.rule.string,
+                "valid": entry.violations.is_empty(),
+                "violations": entry.violations.iter().map(|violation| json!({
+                    "policy": violation.policy,
+                    "reason": violation.reason,
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&entries)?);
+    Ok(())
+}
+
+fn load(
+    args: &PolicyArgs,
+    inputs_dir: Option<&Path>,
+) -> Result<(Policies, PasswordDatabase), Box<dyn Error>> {
+    let policies = args.policies()?;
+    let (input, contents) = read_input(2, args.input.clone(), inputs_dir)?;
+    let database =
+        PasswordDatabase::parse(&contents).map_err(|err| format!("{}: {}", input, err))?;
+    Ok((policies, database))
+}
+
+pub fn run(command: Day2Command, inputs_dir: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    match command {
+        Day2Command::Validate(args) => {
+            let (policies, database) = load(&args, inputs_dir)?;
+            println!("{}", database.count_valid(&policies));
+        }
+        Day2Command::Report {
+            policy,
+            format,
+            all,
+        } => {
+            let (policies, database) = load(&policy, inputs_dir)?;
+            let entries: Vec<_> = database
+                .report(&policies)
+                .filter(|entry| all || !entry.violations.is_empty())
+                .collect();
+            match format {
+                Format::Table => print_table(&entries),
+                Format::Csv => print_csv(&entries)?,
+                Format::Json => print_json(&entries)?,
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use aoc_2020_3::{Boundary, Map, RaggedRows};
+use aoc_common::Input;
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::read_input;
+
+#[derive(Args)]
+pub struct MapArgs {
+    /// What happens at the edges of the map: wrap, toroidal, clamp or stop
+    #[arg(long, default_value = "wrap")]
+    boundary: Boundary,
+    /// Fill up short rows with open squares instead of rejecting the map
+    #[arg(long)]
+    pad: bool,
+    /// Input file or - for stdin
+    #[arg(long)]
+    input: Option<Input>,
+}
+
+impl MapArgs {
+    fn load(self, inputs_dir: Option<&Path>) -> Result<Map, Box<dyn Error>> {
+        let (input, contents) = read_input(3, self.input, inputs_dir)?;
+        let ragged = if self.pad {
+            RaggedRows::Pad
+        } else {
+            RaggedRows::Reject
+        };
+        let map =
+            Map::parse_rows(&contents, ragged).map_err(|err| format!("{}: {}", input, err))?;
+        Ok(map.with_boundary(self.boundary))
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Day3Command {
+    /// Count the trees of every slope and rank them from the fewest to the most trees
+    Slopes {
+        #[arg(long, default_value_t = 7)]
+        max_dx: usize,
+        #[arg(long, default_value_t = 2)]
+        max_dy: usize,
+        /// Only print the given number of slopes with the fewest trees
+        #[arg(long)]
+        top: Option<usize>,
+        #[command(flatten)]
+        map: MapArgs,
+    },
+    /// Draw the path of a slope over the map, O marks hit trees and X clear squares
+    Render {
+        #[arg(long, default_value_t = 3)]
+        dx: usize,
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        dy: u64,
+        #[arg(long, value_enum, default_value_t = Color::Auto)]
+        color: Color,
+        /// Write an SVG image to the given file instead of printing the map
+        #[arg(long)]
+        svg: Option<PathBuf>,
+        /// Size of a square in the SVG image in pixels
+        #[arg(long, default_value_t = 8)]
+        cell: usize,
+        #[command(flatten)]
+        map: MapArgs,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Color {
+    /// Color if stdout is a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+pub fn run(command: Day3Command, inputs_dir: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    match command {
+        Day3Command::Slopes {
+            max_dx,
+            max_dy,
+            top,
+            map,
+        } => {
+            let map = map.load(inputs_dir)?;
+            let counts = map.sweep_slopes(max_dx, max_dy);
+            if counts.is_empty() {
+                return Err("no slopes within the bounds, --max-dy has to be at least 1".into());
+            }
+            println!("{:>4} {:>4} {:>4} {:>6}", "rank", "dx", "dy", "trees");
+            for (rank, count) in counts.iter().take(top.unwrap_or(counts.len())).enumerate() {
+                println!(
+                    "{:>4} {:>4} {:>4} {:>6}",
+                    rank + 1,
+                    count.dx,
+                    count.dy,
+                    count.trees
+                );
+            }
+            let fewest = counts[0];
+            println!(
+                "fewest trees: right {}, down {} with {} trees",
+                fewest.dx, fewest.dy, fewest.trees
+            );
+        }
+        Day3Command::Render {
+            dx,
+            dy,
+            color,
+            svg,
+            cell,
+            map,
+        } => {
+            let map = map.load(inputs_dir)?;
+            let rendering = map.render(dx, dy as usize);
+            if let Some(path) = svg {
+                fs::write(&path, rendering.to_svg(cell))
+                    .map_err(|err| format!("{}: {}", path.display(), err))?;
+                return Ok(());
+            }
+            let color = match color {
+                Color::Auto => io::stdout().is_terminal(),
+                Color::Always => true,
+                Color::Never => false,
+            };
+            if color {
+                print!("{}", rendering.to_ansi());
+            } else {
+                print!("{}", rendering);
+            }
+            println!("{} trees", map.check_slope(dx, dy as usize));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_2020_4::{
+    check_csv_exportable, check_exportable, read_csv, read_json_lines, write_batch, write_csv,
+    write_json_lines, Batch, Mode, Passport, ReadError, Records, Schema,
+};
+use aoc_common::Input;
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::{read_input, resolve_input};
+
+#[derive(Args)]
+pub struct SchemaArgs {
+    /// Schema file in TOML or, with a .json extension, JSON. Defaults to the rules of part 2
+    #[arg(long)]
+    schema: Option<PathBuf>,
+    /// Keep fields which are not part of the puzzle instead of rejecting the batch
+    #[arg(long)]
+    lenient: bool,
+    /// Input file or - for stdin
+    #[arg(long)]
+    input: Option<Input>,
+}
+
+impl SchemaArgs {
+    fn mode(&self) -> Mode {
+        if self.lenient {
+            Mode::Lenient
+        } else {
+            Mode::Strict
+        }
+    }
+
+    fn schema(&self) -> Result<Schema, Box<dyn Error>> {
+        let path = match &self.schema {
+            Some(path) => path,
+            None => return Ok(Schema::default()),
+        };
+        let contents =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        let schema = if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            Schema::from_json(&contents)
+        } else {
+            Schema::from_toml(&contents)
+        };
+        Ok(schema.map_err(|err| format!("{}: {}", path.display(), err))?)
+    }
+
+    // Calls `f` with the index of every passport, reading one passport at a time. Records which
+    // can't be parsed are reported on stderr and skipped, returns their number.
+    fn for_each_passport(
+        &self,
+        inputs_dir: Option<&Path>,
+        mut f: impl FnMut(usize, &Schema, Passport),
+    ) -> Result<usize, Box<dyn Error>> {
+        let schema = self.schema()?;
+        let input = resolve_input(4, self.input.clone(), inputs_dir);
+        let mut skipped = 0;
+        for (i, passport) in Records::new(input.open()?, self.mode()).enumerate() {
+            match passport {
+                Ok(passport) => f(i, &schema, passport),
+                Err(ReadError::Parse(err)) => {
+                    eprintln!("{}: passport {}: {}", input, i + 1, err);
+                    skipped += 1;
+                }
+                Err(err) => return Err(format!("{}: {}", input, err).into()),
+            }
+        }
+        Ok(skipped)
+    }
+}
+
+// Fails if records were skipped, after the results of the others are printed
+fn check_skipped(skipped: usize) -> Result<(), Box<dyn Error>> {
+    if skipped > 0 {
+        return Err(format!("skipped {} passports which could not be parsed", skipped).into());
+    }
+    Ok(())
+}
+
+#[derive(Subcommand)]
+pub enum Day4Command {
+    /// Count the passports which are valid according to a schema
+    Validate(SchemaArgs),
+    /// List the missing and invalid fields of every invalid passport
+    Report {
+        #[command(flatten)]
+        schema: SchemaArgs,
+        /// Include the valid passports
+        #[arg(long)]
+        all: bool,
+    },
+    /// Convert passports between the batch format, JSON lines and CSV
+    Convert {
+        #[arg(long, value_enum, default_value_t = Format::Batch)]
+        from: Format,
+        #[arg(long, value_enum)]
+        to: Format,
+        /// Keep fields which are not part of the puzzle instead of rejecting the batch
+        #[arg(long)]
+        lenient: bool,
+        /// Input file or - for stdin
+        #[arg(long)]
+        input: Option<Input>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// key:value fields with a blank line between passports, as in the puzzle
+    Batch,
+    /// One JSON object per passport and line
+    Json,
+    /// One row per passport with a column per field
+    Csv,
+}
+
+pub fn run(command: Day4Command, inputs_dir: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    match command {
+        Day4Command::Validate(args) => {
+            let mut valid = 0;
+            let skipped = args.for_each_passport(inputs_dir, |_, schema, passport| {
+                if passport.is_valid(schema) {
+                    valid += 1;
+                }
+            })?;
+            println!("{}", valid);
+            check_skipped(skipped)?;
+        }
+        Day4Command::Report { schema, all } => {
+            let mut valid = 0;
+            let mut total = 0;
+            let skipped = schema.for_each_passport(inputs_dir, |i, schema, passport| {
+                total += 1;
+                match passport.validate(schema) {
+                    Ok(()) => {
+                        valid += 1;
+                        if all {
+                            println!("passport {}: valid", i + 1);
+                        }
+                    }
+                    Err(errors) => {
+                        println!("passport {}: invalid", i + 1);
+                        for error in errors {
+                            println!("  {}", error);
+                        }
+                    }
+                }
+            })?;
+            println!("{} of {} passports valid", valid, total);
+            check_skipped(skipped)?;
+        }
+        Day4Command::Convert {
+            from,
+            to,
+            lenient,
+            input,
+        } => {
+            let mode = if lenient { Mode::Lenient } else { Mode::Strict };
+            let (input, contents) = read_input(4, input, inputs_dir)?;
+            let passports = match from {
+                Format::Batch => Batch::parse_with(&contents, mode).map(Batch::into_passports),
+                Format::Json => read_json_lines(&contents, mode),
+                Format::Csv => read_csv(&contents, mode),
+            }
+            .map_err(|err| format!("{}: {}", input, err))?;
+            match to {
+                Format::Csv => check_csv_exportable(&passports),
+                _ => check_exportable(&passports),
+            }
+            .map_err(|err| format!("{}: {}", input, err))?;
+            let stdout = io::stdout().lock();
+            match to {
+                Format::Batch => write_batch(stdout, &passports)?,
+                Format::Json => write_json_lines(stdout, &passports)?,
+                Format::Csv => write_csv(stdout, &passports)?,
+            }
+        }
+    }
+    Ok(())
+}
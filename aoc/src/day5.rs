@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::path::Path;
+
+use aoc_2020_5::{BoardingPass, Geometry, Missing, Seats};
+use aoc_common::Input;
+use clap::{Args, Subcommand};
+
+use crate::read_input;
+
+#[derive(Args)]
+pub struct GeometryArgs {
+    /// Number of rows of the plane, a power of two
+    #[arg(long, default_value_t = 128)]
+    rows: u32,
+    /// Number of columns of the plane, a power of two
+    #[arg(long, default_value_t = 8)]
+    columns: u32,
+}
+
+impl GeometryArgs {
+    fn geometry(&self) -> Result<Geometry, Box<dyn Error>> {
+        Ok(Geometry::new(self.rows, self.columns)?)
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Day5Command {
+    /// Print row, column and seat ID of seat codes
+    Decode {
+        #[command(flatten)]
+        geometry: GeometryArgs,
+        #[arg(required = true)]
+        codes: Vec<String>,
+    },
+    /// Print the seat code of a row and column
+    Encode {
+        #[command(flatten)]
+        geometry: GeometryArgs,
+        #[arg(long)]
+        row: u32,
+        #[arg(long)]
+        column: u32,
+    },
+    /// Draw the seat map, # marks occupied seats, O gaps between them and . missing seats in
+    /// front and behind
+    Map {
+        #[command(flatten)]
+        geometry: GeometryArgs,
+        /// Input file or - for stdin
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    /// List the seats without a boarding pass
+    Missing {
+        #[command(flatten)]
+        geometry: GeometryArgs,
+        /// Input file or - for stdin
+        #[arg(long)]
+        input: Option<Input>,
+    },
+}
+
+fn load(
+    geometry: &GeometryArgs,
+    input: Option<Input>,
+    inputs_dir: Option<&Path>,
+) -> Result<Seats, Box<dyn Error>> {
+    let geometry = geometry.geometry()?;
+    let (input, contents) = read_input(5, input, inputs_dir)?;
+    let seats =
+        Seats::parse_with(&contents, geometry).map_err(|err| format!("{}: {}", input, err))?;
+    Ok(seats)
+}
+
+fn print_rows(name: &str, passes: &[BoardingPass]) {
+    if let (Some(first), Some(last)) = (passes.first(), passes.last()) {
+        println!(
+            "{}: {} seats from row {} column {} to row {} column {}",
+            name,
+            passes.len(),
+            first.row(),
+            first.column(),
+            last.row(),
+            last.column()
+        );
+    }
+}
+
+pub fn run(command: Day5Command, inputs_dir: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    match command {
+        Day5Command::Decode { geometry, codes } => {
+            let geometry = geometry.geometry()?;
+            for code in codes {
+                let pass = BoardingPass::decode(&code, geometry)
+                    .map_err(|err| format!("{}: {}", code, err))?;
+                println!(
+                    "{}: row {}, column {}, seat ID {}",
+                    code,
+                    pass.row(),
+                    pass.column(),
+                    pass.seat_id()
+                );
+            }
+        }
+        Day5Command::Encode {
+            geometry,
+            row,
+            column,
+        } => {
+            let pass = BoardingPass::new(geometry.geometry()?, row, column)?;
+            println!("{}", pass);
+        }
+        Day5Command::Map { geometry, input } => {
+            print!("{}", load(&geometry, input, inputs_dir)?.render());
+        }
+        Day5Command::Missing { geometry, input } => {
+            let seats = load(&geometry, input, inputs_dir)?;
+            let missing = seats.missing();
+            let passes = |kind| -> Vec<_> {
+                missing
+                    .iter()
+                    .filter(|seat| seat.missing == kind)
+                    .map(|seat| seat.pass)
+                    .collect()
+            };
+            print_rows("front", &passes(Missing::Front));
+            let interior = passes(Missing::Interior);
+            for gap in seats.gaps() {
+                let seats: Vec<_> = interior
+                    .iter()
+                    .filter(|pass| gap.contains(&pass.seat_id()))
+                    .map(|pass| format!("{} ({})", pass.seat_id(), pass))
+                    .collect();
+                println!("gap: {}", seats.join(", "));
+            }
+            print_rows("back", &passes(Missing::Back));
+        }
+    }
+    Ok(())
+}
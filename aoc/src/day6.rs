@@ -0,0 +1,146 @@
+use std::error::Error;
+use std::io;
+use std::path::Path;
+
+use aoc_2020_6::{CustomsForms, Group};
+use aoc_common::Input;
+use clap::{Subcommand, ValueEnum};
+
+use crate::read_input;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Quorum {
+    /// Answered by anyone in the group (part 1)
+    Anyone,
+    /// Answered by everyone in the group (part 2)
+    Everyone,
+    /// Answered by at least half of the group
+    Half,
+    /// Answered by more than half of the group
+    Majority,
+}
+
+#[derive(Subcommand)]
+pub enum Day6Command {
+    /// Sum the questions per group which reach a quorum
+    Count {
+        #[arg(long, value_enum, default_value_t = Quorum::Anyone, conflicts_with = "at_least")]
+        quorum: Quorum,
+        /// Count the questions answered by at least this many people of a group
+        #[arg(long)]
+        at_least: Option<usize>,
+        /// Input file or - for stdin
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    /// Show per question statistics and the largest and most unanimous groups
+    Stats {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+        /// Number of groups to list, only for the table
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+        /// Input file or - for stdin
+        #[arg(long)]
+        input: Option<Input>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    /// The per question statistics as CSV
+    Csv,
+}
+
+fn print_table(forms: &CustomsForms, top: usize) {
+    let people = forms.people().max(1) as f64;
+    println!(
+        "{:<8} {:>14} {:>16} {:>8} {:>10}",
+        "question", "groups anyone", "groups everyone", "people", "frequency"
+    );
+    for stats in forms.question_stats() {
+        println!(
+            "{:<8} {:>14} {:>16} {:>8} {:>9.1}%",
+            stats.question,
+            stats.groups_anyone,
+            stats.groups_everyone,
+            stats.people,
+            stats.people as f64 * 100.0 / people
+        );
+    }
+
+    println!();
+    println!("largest groups:");
+    for (number, group) in forms.largest_groups(top) {
+        println!("  group {}: {} people", number, group.len());
+    }
+    println!("most unanimous groups:");
+    for (number, group) in forms.most_unanimous_groups(top) {
+        println!(
+            "  group {}: {} people all answered {:?}",
+            number,
+            group.len(),
+            group.everyone().questions().collect::<String>()
+        );
+    }
+}
+
+fn print_csv(forms: &CustomsForms) -> Result<(), Box<dyn Error>> {
+    let people = forms.people();
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    writer.write_record([
+        "question",
+        "groups_anyone",
+        "groups_everyone",
+        "people",
+        "people_total",
+    ])?;
+    for stats in forms.question_stats() {
+        writer.write_record([
+            stats.question.to_string(),
+            stats.groups_anyone.to_string(),
+            stats.groups_everyone.to_string(),
+            stats.people.to_string(),
+            people.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn load(input: Option<Input>, inputs_dir: Option<&Path>) -> Result<CustomsForms, Box<dyn Error>> {
+    let (input, contents) = read_input(6, input, inputs_dir)?;
+    let forms =
+        CustomsForms::parse_validated(&contents).map_err(|err| format!("{}: {}", input, err))?;
+    Ok(forms)
+}
+
+pub fn run(command: Day6Command, inputs_dir: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    match command {
+        Day6Command::Count {
+            quorum,
+            at_least,
+            input,
+        } => {
+            let forms = load(input, inputs_dir)?;
+            let count = |group: &Group| match (at_least, quorum) {
+                (Some(k), _) => group.at_least(k),
+                (None, Quorum::Anyone) => group.anyone(),
+                (None, Quorum::Everyone) => group.everyone(),
+                (None, Quorum::Half) => group.at_least(group.len().div_ceil(2)),
+                (None, Quorum::Majority) => group.majority(),
+            };
+            let sum: usize = forms.groups().iter().map(|group| count(group).len()).sum();
+            println!("{}", sum);
+        }
+        Day6Command::Stats { format, top, input } => {
+            let forms = load(input, inputs_dir)?;
+            match format {
+                Format::Table => print_table(&forms, top),
+                Format::Csv => print_csv(&forms)?,
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::error::Error;
+use std::path::Path;
+
+use aoc_2020_7::Rules;
+use aoc_common::Input;
+use clap::Subcommand;
+
+use crate::read_input;
+
+#[derive(Subcommand)]
+pub enum Day7Command {
+    /// List every color which eventually contains a bag of a color
+    Containers {
+        #[arg(long, default_value = "shiny gold")]
+        color: String,
+        /// Input file or - for stdin
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    /// List every color inside a bag of a color with the total number of such bags
+    Contents {
+        #[arg(long, default_value = "shiny gold")]
+        color: String,
+        /// Input file or - for stdin
+        #[arg(long)]
+        input: Option<Input>,
+    },
+}
+
+fn load(input: Option<Input>, inputs_dir: Option<&Path>) -> Result<Rules, Box<dyn Error>> {
+    let (input, contents) = read_input(7, input, inputs_dir)?;
+    let rules = Rules::parse_validated(&contents).map_err(|errors| {
+        errors
+            .iter()
+            .map(|err| format!("{}: {}", input, err))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+    Ok(rules)
+}
+
+fn unknown(color: &str) -> String {
+    format!("no rule mentions {} bags", color)
+}
+
+pub fn run(command: Day7Command, inputs_dir: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    match command {
+        Day7Command::Containers { color, input } => {
+            let rules = load(input, inputs_dir)?;
+            let containers = rules
+                .graph()
+                .containers_of(&color)
+                .ok_or_else(|| unknown(&color))?;
+            for container in &containers {
+                println!("{}", container);
+            }
+            println!("{} colors", containers.len());
+        }
+        Day7Command::Contents { color, input } => {
+            let rules = load(input, inputs_dir)?;
+            let graph = rules.graph();
+            let contents = graph.contents_of(&color).ok_or_else(|| unknown(&color))?;
+            for (content, count) in contents {
+                println!("{:>8} {}", count, content);
+            }
+            let total = graph
+                .total_bags_inside(&color)
+                .ok_or_else(|| unknown(&color))?;
+            println!("{:>8} bags in total", total);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc_common::{parse_boxed, Input, ParseError, Solution, INPUTS_DIR_VAR};
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod verify;
+
+type DayParser = fn(&str) -> Result<Box<dyn Solution>, ParseError>;
+
+const DAYS: [DayParser; 14] = [
+    parse_boxed::<aoc_2020_1::ExpenseReport>,
+    parse_boxed::<aoc_2020_2::PasswordDatabase>,
+    parse_boxed::<aoc_2020_3::Map>,
+    parse_boxed::<aoc_2020_4::Batch>,
+    parse_boxed::<aoc_2020_5::Seats>,
+    parse_boxed::<aoc_2020_6::CustomsForms>,
+    parse_boxed::<aoc_2020_7::Rules>,
+    parse_boxed::<aoc_2020_8::Program>,
+    parse_boxed::<aoc_2020_9::XmasData>,
+    parse_boxed::<aoc_2020_10::Adapters>,
+    parse_boxed::<aoc_2020_11::Map>,
+    parse_boxed::<aoc_2020_12::Instructions>,
+    parse_boxed::<aoc_2020_13::Notes>,
+    parse_boxed::<aoc_2020_14::Program>,
+];
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2020 runner")]
+struct Cli {
+    /// Directory with one dayNN.txt input per day
+    #[arg(long, global = true, env = INPUTS_DIR_VAR)]
+    inputs_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution of a single day
+    Run {
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=DAYS.len() as i64))]
+        day: u32,
+        /// Only run the given part, runs both parts if omitted
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        /// Input file or - for stdin, defaults to the inputs directory or aoc-2020-<day>/input.txt
+        #[arg(long)]
+        input: Option<Input>,
+    },
+    /// Check the solutions against a file of known answers
+    Verify {
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+        /// Only verify the given day
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=DAYS.len() as i64))]
+        day: Option<u32>,
+    },
+    /// Time parsing and both parts of every day
+    Bench {
+        /// Only benchmark the given days, all days without part 2 of day 13 (which does not finish
+        /// on the real input) if omitted
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=DAYS.len() as i64))]
+        day: Vec<u32>,
+        /// Only benchmark the given part, both parts if omitted
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+        /// Print the results as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the password database against configurable policies
+    Day2 {
+        #[command(subcommand)]
+        command: day2::Day2Command,
+    },
+    /// Analyse the tree map with other slopes
+    Day3 {
+        #[command(subcommand)]
+        command: day3::Day3Command,
+    },
+    /// Check the passports against a schema
+    Day4 {
+        #[command(subcommand)]
+        command: day4::Day4Command,
+    },
+    /// Decode and encode boarding passes and find missing seats
+    Day5 {
+        #[command(subcommand)]
+        command: day5::Day5Command,
+    },
+    /// Analyse the customs forms
+    Day6 {
+        #[command(subcommand)]
+        command: day6::Day6Command,
+    },
+    /// Query which bags contain or are inside a bag
+    Day7 {
+        #[command(subcommand)]
+        command: day7::Day7Command,
+    },
+}
+
+// The given input, the input in the inputs directory or aoc-2020-<day>/input.txt
+fn resolve_input(day: u32, input: Option<Input>, inputs_dir: Option<&Path>) -> Input {
+    let default = format!("aoc-2020-{}/input.txt", day);
+    Input::resolve(input, inputs_dir, day, default)
+}
+
+fn read_input(
+    day: u32,
+    input: Option<Input>,
+    inputs_dir: Option<&Path>,
+) -> Result<(Input, String), Box<dyn Error>> {
+    let input = resolve_input(day, input, inputs_dir);
+    let contents = input.read_to_string()?;
+    Ok((input, contents))
+}
+
+fn parse_day(day: u32, input: &Input, contents: &str) -> Result<Box<dyn Solution>, Box<dyn Error>> {
+    let solution = DAYS[day as usize - 1](contents).map_err(|err| format!("{}: {}", input, err))?;
+    Ok(solution)
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let inputs_dir = cli.inputs_dir.as_deref();
+    match cli.command {
+        Command::Run { day, part, input } => {
+            let (input, contents) = read_input(day, input, inputs_dir)?;
+            let solution = parse_day(day, &input, &contents)?;
+            match part {
+                Some(1) => println!("Part 1: {}", solution.part_1()),
+                Some(_) => println!("Part 2: {}", solution.part_2()),
+                None => aoc_common::print_answers(solution.as_ref()),
+            }
+        }
+        Command::Verify { answers, day } => {
+            if !verify::verify(&answers, day, inputs_dir)? {
+                return Err("some answers did not match".into());
+            }
+        }
+        Command::Bench {
+            day,
+            part,
+            iterations,
+            json,
+        } => {
+            let all_days = day.is_empty();
+            let days = if all_days {
+                (1..=DAYS.len() as u32).collect()
+            } else {
+                day
+            };
+            let parts = part.map_or(vec![1, 2], |part| vec![part]);
+
+            let mut timings = Vec::new();
+            for day in days {
+                let parts: Vec<_> = parts
+                    .iter()
+                    .copied()
+                    .filter(|&part| !(all_days && bench::SLOW_PARTS.contains(&(day, part))))
+                    .collect();
+                timings.extend(bench::bench_day(
+                    day,
+                    &parts,
+                    iterations as usize,
+                    inputs_dir,
+                )?);
+            }
+            if json {
+                println!("{}", serde_json::to_string_pretty(&timings)?);
+            } else {
+                bench::print_table(&timings);
+            }
+        }
+        Command::Day2 { command } => day2::run(command, inputs_dir)?,
+        Command::Day3 { command } => day3::run(command, inputs_dir)?,
+        Command::Day4 { command } => day4::run(command, inputs_dir)?,
+        Command::Day5 { command } => day5::run(command, inputs_dir)?,
+        Command::Day6 { command } => day6::run(command, inputs_dir)?,
+        Command::Day7 { command } => day7::run(command, inputs_dir)?,
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc_common::Answer;
+use serde::Deserialize;
+
+use crate::{parse_day, read_input};
+
+/// Expected answers of one day, parts without an answer are skipped.
+///
+/// ```toml
+/// [day08]
+/// part_1 = 1451
+/// part_2 = 1160
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Expected {
+    part_1: Option<toml::Value>,
+    part_2: Option<toml::Value>,
+}
+
+fn load_answers(path: &Path) -> Result<BTreeMap<u32, Expected>, Box<dyn Error>> {
+    let contents =
+        fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let answers: BTreeMap<String, Expected> =
+        toml::from_str(&contents).map_err(|err| format!("{}: {}", path.display(), err))?;
+    answers
+        .into_iter()
+        .map(|(key, expected)| {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .filter(|day| (1..=crate::DAYS.len() as u32).contains(day))
+                .ok_or_else(|| format!("{}: invalid day {:?}", path.display(), key))?;
+            Ok((day, expected))
+        })
+        .collect()
+}
+
+fn expected_text(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn check_part(day: u32, part: u32, expected: &toml::Value, solve: impl FnOnce() -> Answer) -> bool {
+    let start = Instant::now();
+    let answer = solve();
+    let elapsed = start.elapsed();
+
+    let expected = expected_text(expected);
+    let passed = answer.to_string() == expected;
+    if passed {
+        println!(
+            "day {:02} part {}: ok ({}) in {:?}",
+            day, part, answer, elapsed
+        );
+    } else {
+        println!(
+            "day {:02} part {}: FAILED expected {}, got {} in {:?}",
+            day, part, expected, answer, elapsed
+        );
+    }
+    passed
+}
+
+/// Runs every day with known answers and prints a line per part, returns whether all passed.
+pub fn verify(
+    answers: &Path,
+    only_day: Option<u32>,
+    inputs_dir: Option<&Path>,
+) -> Result<bool, Box<dyn Error>> {
+    let answers = load_answers(answers)?;
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut total = Duration::default();
+    for (&day, expected) in answers.iter() {
+        if only_day.is_some_and(|only_day| only_day != day) {
+            continue;
+        }
+
+        let start = Instant::now();
+        let solution = read_input(day, None, inputs_dir)
+            .and_then(|(input, contents)| parse_day(day, &input, &contents));
+        let solution = match solution {
+            Ok(solution) => solution,
+            Err(err) => {
+                println!("day {:02}: FAILED {}", day, err);
+                failed += 1;
+                continue;
+            }
+        };
+        println!("day {:02} parse: ok in {:?}", day, start.elapsed());
+
+        let mut results = Vec::new();
+        if let Some(expected) = &expected.part_1 {
+            results.push(check_part(day, 1, expected, || solution.part_1()));
+        }
+        if let Some(expected) = &expected.part_2 {
+            results.push(check_part(day, 2, expected, || solution.part_2()));
+        }
+        for result in results {
+            if result {
+                passed += 1;
+            } else {
+                failed += 1;
+            }
+        }
+
+        total += start.elapsed();
+    }
+
+    println!("{} passed, {} failed in {:?}", passed, failed, total);
+    Ok(failed == 0)
+}